@@ -0,0 +1,81 @@
+use ::criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ::parse_duration0::{parse, parse_with, Options, Parser};
+
+const INPUTS: [&str; 6] = [
+    "15",
+    "1h30m",
+    "1 day -1 hour",
+    "Duration: 1 hour, 15 minutes and 29.5 seconds",
+    "15days20seconds100milliseconds",
+    "1½ hours 3/4 min",
+];
+
+fn parse_inputs(c: &mut Criterion) {
+    c.bench_function("parse", |b| {
+        b.iter(|| {
+            for input in INPUTS {
+                let _ = black_box(parse(black_box(input)));
+            }
+        })
+    });
+}
+
+fn parse_natural_language(c: &mut Criterion) {
+    let options = Options::new().natural_language(true);
+    let inputs = ["two and a half hours", "half an hour and twenty minutes"];
+    c.bench_function("parse_with natural language", |b| {
+        b.iter(|| {
+            for input in inputs {
+                let _ = black_box(parse_with(black_box(input), &options));
+            }
+        })
+    });
+    let mut parser = Parser::new(options);
+    c.bench_function("Parser natural language", |b| {
+        b.iter(|| {
+            for input in inputs {
+                let _ = black_box(parser.parse(black_box(input)));
+            }
+        })
+    });
+}
+
+fn parse_many(c: &mut Criterion) {
+    let inputs: Vec<_> = INPUTS.iter().cycle().take(1_000).collect();
+    let mut parser = Parser::new(Options::new());
+    c.bench_function("Parser::parse_many", |b| {
+        b.iter(|| {
+            parser
+                .parse_many(black_box(&inputs))
+                .filter(Result::is_ok)
+                .count()
+        })
+    });
+}
+
+fn parse_lines(c: &mut Criterion) {
+    let lines: String = INPUTS
+        .iter()
+        .cycle()
+        .take(1_000)
+        .map(|input| format!("{}\n", input))
+        .collect();
+    let mut parser = Parser::new(Options::new());
+    c.bench_function("Parser::parse_lines", |b| {
+        b.iter(|| {
+            parser
+                .parse_lines(black_box(lines.as_bytes()))
+                .filter(|line| matches!(line, Ok((_, Ok(_)))))
+                .count()
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    parse_inputs,
+    parse_natural_language,
+    parse_many,
+    parse_lines
+);
+criterion_main!(benches);
@@ -0,0 +1,86 @@
+#ifndef PARSE_DURATION_H
+#define PARSE_DURATION_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The result of `pd_parse`. Everything except `Ok` is an error.
+ *
+ * The values from 10 up correspond to the variants of `parse::Error`.
+ */
+typedef enum PdStatus {
+  PD_STATUS_OK = 0,
+  /**
+   * The input or an output pointer was null.
+   */
+  PD_STATUS_NULL_POINTER = 1,
+  /**
+   * The input was not valid UTF-8.
+   */
+  PD_STATUS_INVALID_UTF8 = 2,
+  PD_STATUS_PARSE_INT = 10,
+  PD_STATUS_UNKNOWN_UNIT = 11,
+  PD_STATUS_OUT_OF_BOUNDS = 12,
+  PD_STATUS_OVERFLOW = 13,
+  PD_STATUS_EXP_NOT_SUPPORTED = 14,
+  PD_STATUS_NO_UNIT_FOUND = 15,
+  PD_STATUS_NO_VALUE_FOUND = 16,
+  PD_STATUS_DIVISION_BY_ZERO = 17,
+  PD_STATUS_UNEXPECTED_TOKEN = 18,
+  PD_STATUS_UNEXPECTED_END = 19,
+  PD_STATUS_INVALID_OPERATION = 20,
+  PD_STATUS_NESTING_TOO_DEEP = 21,
+  PD_STATUS_INVALID_RANGE = 22,
+  PD_STATUS_TOO_SHORT = 23,
+  PD_STATUS_TOO_LONG = 24,
+  PD_STATUS_UNIT_NOT_ALLOWED = 25,
+  PD_STATUS_ZERO_NOT_ALLOWED = 26,
+  PD_STATUS_INVALID_FORMAT = 27,
+  PD_STATUS_INPUT_TOO_LONG = 28,
+  PD_STATUS_TOO_MANY_COMPONENTS = 29,
+  PD_STATUS_AMBIGUOUS_UNIT = 30,
+} PdStatus;
+
+
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Parse the `len` bytes at `input` as a duration, and store it in `secs` and `nanos`.
+ *
+ * The input does not need to be nul-terminated. On error, `secs` and `nanos` are not changed,
+ * and `pd_last_error` gives the message.
+ *
+ * # Safety
+ *
+ * `input` must point to `len` readable bytes, and `secs` and `nanos` must be valid for writes.
+ */
+enum PdStatus pd_parse(const char *input, size_t len, uint64_t *secs, uint32_t *nanos);
+
+/**
+ * Get the error of the last `pd_parse` call on this thread, and return its status.
+ *
+ * `message` is set to a nul-terminated description, which stays valid until the next call of
+ * `pd_parse` on this thread. `span_start` and `span_end` are set to the byte range of the input
+ * that the error is about. If the last call succeeded, the message is null and the span is empty.
+ * Any of the pointers may be null, to leave that value out.
+ *
+ * # Safety
+ *
+ * The pointers must be null or valid for writes.
+ */
+enum PdStatus pd_last_error(const char **message, size_t *span_start, size_t *span_end);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* PARSE_DURATION_H */
@@ -0,0 +1,62 @@
+/* Tests the C interface. Run with ffi/test.sh. */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "parse_duration.h"
+
+static int failures = 0;
+
+#define CHECK(condition)                                                  \
+    do {                                                                  \
+        if (!(condition)) {                                               \
+            fprintf(stderr, "%s:%d: failed: %s\n", __FILE__, __LINE__, #condition); \
+            failures++;                                                   \
+        }                                                                 \
+    } while (0)
+
+static PdStatus parse(const char *input, uint64_t *secs, uint32_t *nanos) {
+    return pd_parse(input, strlen(input), secs, nanos);
+}
+
+int main(void) {
+    uint64_t secs = 0;
+    uint32_t nanos = 0;
+    const char *message = NULL;
+    size_t start = 0, end = 0;
+
+    CHECK(parse("1 hour 30 minutes 0.5 s", &secs, &nanos) == PD_STATUS_OK);
+    CHECK(secs == 5400 && nanos == 500000000);
+    CHECK(pd_last_error(&message, &start, &end) == PD_STATUS_OK);
+    CHECK(message == NULL);
+
+    /* The input does not need to be nul-terminated. */
+    CHECK(pd_parse("15mXXX", 3, &secs, &nanos) == PD_STATUS_OK);
+    CHECK(secs == 900 && nanos == 0);
+
+    CHECK(parse("5 min 3 m 2 blorps", &secs, &nanos) == PD_STATUS_UNKNOWN_UNIT);
+    CHECK(secs == 900);
+    CHECK(pd_last_error(&message, &start, &end) == PD_STATUS_UNKNOWN_UNIT);
+    CHECK(strcmp(message, "UnknownUnitError: \"blorps\" is not a known unit") == 0);
+    CHECK(start == 12 && end == 18);
+
+    CHECK(parse("1e5 s", &secs, &nanos) == PD_STATUS_EXP_NOT_SUPPORTED);
+    CHECK(parse("1 day -2 days", &secs, &nanos) == PD_STATUS_OUT_OF_BOUNDS);
+    CHECK(parse("nothing", &secs, &nanos) == PD_STATUS_NO_VALUE_FOUND);
+    CHECK(pd_last_error(NULL, &start, &end) == PD_STATUS_NO_VALUE_FOUND);
+    CHECK(start == 0 && end == 7);
+
+    CHECK(pd_parse("1 \xff s", 6, &secs, &nanos) == PD_STATUS_INVALID_UTF8);
+    CHECK(pd_last_error(NULL, &start, &end) == PD_STATUS_INVALID_UTF8);
+    CHECK(start == 2 && end == 3);
+
+    CHECK(pd_parse(NULL, 0, &secs, &nanos) == PD_STATUS_NULL_POINTER);
+    CHECK(parse("1s", NULL, &nanos) == PD_STATUS_NULL_POINTER);
+
+    if (failures > 0) {
+        fprintf(stderr, "%d checks failed\n", failures);
+        return 1;
+    }
+    printf("all checks passed\n");
+    return 0;
+}
@@ -0,0 +1,9 @@
+#!/bin/sh
+# Builds the library as a static library and runs the C tests against it.
+set -eu
+cd "$(dirname "$0")/.."
+cargo rustc --lib --features ffi --crate-type staticlib
+mkdir -p target/ffi
+${CC:-cc} -std=c99 -Wall -Wextra -Werror -Iffi ffi/test.c target/debug/libparse_duration0.a \
+    -lpthread -ldl -lm -o target/ffi/test
+target/ffi/test
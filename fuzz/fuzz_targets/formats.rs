@@ -0,0 +1,35 @@
+//! The parsers of other formats must not panic, and their formatters must write what they read.
+
+#![no_main]
+
+use ::libfuzzer_sys::fuzz_target;
+use ::parse_duration0::{
+    format_dotnet, format_java, format_postgres, format_prometheus, parse_dotnet, parse_go,
+    parse_java, parse_postgres, parse_prometheus, IntervalStyle,
+};
+
+fuzz_target!(|input: &str| {
+    let _ = parse_go(input);
+    if let Ok(duration) = parse_prometheus(input) {
+        assert_eq!(parse_prometheus(&format_prometheus(duration)), Ok(duration));
+    }
+    if let Ok(duration) = parse_java(input) {
+        assert_eq!(parse_java(&format_java(duration)), Ok(duration));
+    }
+    if let Ok(duration) = parse_dotnet(input) {
+        assert_eq!(parse_dotnet(&format_dotnet(duration)), Ok(duration));
+    }
+    for style in [
+        IntervalStyle::Postgres,
+        IntervalStyle::PostgresVerbose,
+        IntervalStyle::SqlStandard,
+        IntervalStyle::Iso8601,
+    ] {
+        if let Ok(interval) = parse_postgres(input, style) {
+            assert_eq!(
+                parse_postgres(&format_postgres(&interval, style), style),
+                Ok(interval)
+            );
+        }
+    }
+});
@@ -0,0 +1,41 @@
+//! `parse` and its configurable variants must not panic, and must take time linear in the input,
+//! unlike the original crate, whose exponents could take hours (RUSTSEC-2021-0041).
+
+#![no_main]
+
+use ::libfuzzer_sys::fuzz_target;
+use ::parse_duration0::{
+    find_durations, parse, parse_expression, parse_hardened, parse_range, parse_with, Options,
+};
+use ::std::time::{Duration, Instant};
+
+/// The time that any input of `len` bytes may take, which is very generous even for debug builds
+/// with sanitizers.
+fn time_limit(len: usize) -> Duration {
+    Duration::from_millis(100) + Duration::from_micros(100) * len as u32
+}
+
+fuzz_target!(|input: &str| {
+    let start = Instant::now();
+    let _ = parse(input);
+    let _ = parse_hardened(input);
+    let _ = parse_with(
+        input,
+        &Options::new()
+            .natural_language(true)
+            .group_separators(&['_']),
+    );
+    let _ = parse_with(input, &Options::new().strict(true).decimal_separator(','));
+    let _ = parse_expression(input);
+    let _ = parse_range(input);
+    for found in find_durations(input) {
+        assert_eq!(parse(&input[found.span]), Ok(found.duration));
+    }
+    let elapsed = start.elapsed();
+    assert!(
+        elapsed < time_limit(input.len()),
+        "{} bytes took {:?}",
+        input.len(),
+        elapsed
+    );
+});
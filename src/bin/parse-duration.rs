@@ -0,0 +1,344 @@
+//! Convert durations like "1h30m" to seconds or other formats, for shell scripts.
+//!
+//! Run `parse-duration --help` for the usage.
+
+use ::std::env;
+use ::std::io::{self, BufRead, Write};
+use ::std::process::ExitCode;
+use ::std::time::Duration;
+
+use ::parse_duration0::{format, format_iso8601, parse_with, Error, Options};
+
+const USAGE: &str = "\
+Usage: parse-duration [OPTIONS] [DURATION]...
+
+Convert durations like \"1h30m\" to seconds or other formats.
+Without DURATION arguments, durations are read from standard input, one per line.
+
+Options:
+  --to FORMAT   secs (default), ms, ns, iso8601 or human
+  --strict      only accept values with units, separated by whitespace
+  --json        print one JSON object per duration, also for errors
+  -h, --help    print this help
+  --version     print the version
+
+Exit codes:
+  0   all durations were valid
+  1   a duration was invalid in a way without a code of its own
+  2   the arguments were invalid
+  3   reading or writing failed
+  10+ the error of the first invalid duration:
+      10 ParseInt, 11 UnknownUnit, 12 OutOfBounds, 13 Overflow, 14 ExpNotSupported,
+      15 NoUnitFound, 16 NoValueFound, 17 DivisionByZero, 18 UnexpectedToken,
+      19 UnexpectedEnd, 20 InvalidOperation, 21 NestingTooDeep, 22 InvalidRange,
+      23 TooShort, 24 TooLong, 25 UnitNotAllowed, 26 ZeroNotAllowed, 27 InvalidFormat,
+      28 InputTooLong, 29 TooManyComponents, 30 AmbiguousUnit";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Output {
+    Seconds,
+    Milliseconds,
+    Nanoseconds,
+    Iso8601,
+    Human,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Arguments {
+    output: Output,
+    strict: bool,
+    json: bool,
+    inputs: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Convert(Arguments),
+    Help,
+    Version,
+}
+
+fn parse_output(name: &str) -> Result<Output, String> {
+    match name {
+        "s" | "sec" | "secs" | "seconds" => Ok(Output::Seconds),
+        "ms" | "millis" | "milliseconds" => Ok(Output::Milliseconds),
+        "ns" | "nanos" | "nanoseconds" => Ok(Output::Nanoseconds),
+        "iso" | "iso8601" => Ok(Output::Iso8601),
+        "human" => Ok(Output::Human),
+        _ => Err(format!("unknown format \"{}\" for --to", name)),
+    }
+}
+
+fn parse_arguments(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut arguments = Arguments {
+        output: Output::Seconds,
+        strict: false,
+        json: false,
+        inputs: Vec::new(),
+    };
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--version" => return Ok(Command::Version),
+            "--strict" => arguments.strict = true,
+            "--json" => arguments.json = true,
+            "--to" => {
+                let name = args.next().ok_or("--to needs a format")?;
+                arguments.output = parse_output(&name)?;
+            }
+            "--" => arguments.inputs.extend(args.by_ref()),
+            _ => {
+                if let Some(name) = arg.strip_prefix("--to=") {
+                    arguments.output = parse_output(name)?;
+                } else if arg.starts_with("--") {
+                    return Err(format!("unknown option \"{}\"", arg));
+                } else {
+                    // Durations like "-5s 1m" are allowed, so only "--" starts an option.
+                    arguments.inputs.push(arg);
+                }
+            }
+        }
+    }
+    Ok(Command::Convert(arguments))
+}
+
+/// The exit code for an invalid duration.
+fn exit_code(error: &Error) -> u8 {
+    match error {
+        Error::ParseInt(_) => 10,
+        Error::UnknownUnit(_) => 11,
+        Error::OutOfBounds(_) => 12,
+        Error::Overflow => 13,
+        Error::ExpNotSupported => 14,
+        Error::NoUnitFound(_) => 15,
+        Error::NoValueFound(_) => 16,
+        Error::DivisionByZero(_) => 17,
+        Error::UnexpectedToken(_) => 18,
+        Error::UnexpectedEnd => 19,
+        Error::InvalidOperation(_) => 20,
+        Error::NestingTooDeep => 21,
+        Error::InvalidRange(_) => 22,
+        Error::TooShort { .. } => 23,
+        Error::TooLong { .. } => 24,
+        Error::UnitNotAllowed { .. } => 25,
+        Error::ZeroNotAllowed => 26,
+        Error::InvalidFormat(_) => 27,
+        Error::InputTooLong { .. } => 28,
+        Error::TooManyComponents { .. } => 29,
+        Error::AmbiguousUnit { .. } => 30,
+        _ => 1,
+    }
+}
+
+/// `nanoseconds` divided by `unit`, with as many decimals as needed.
+fn decimal(nanoseconds: u128, unit: u128) -> String {
+    let whole = nanoseconds / unit;
+    let fraction = nanoseconds % unit;
+    if fraction == 0 {
+        return whole.to_string();
+    }
+    let digits = unit.to_string().len() - 1;
+    let fraction = format!("{:0width$}", fraction, width = digits);
+    format!("{}.{}", whole, fraction.trim_end_matches('0'))
+}
+
+fn convert(duration: Duration, output: Output) -> String {
+    match output {
+        Output::Seconds => decimal(duration.as_nanos(), 1_000_000_000),
+        Output::Milliseconds => decimal(duration.as_nanos(), 1_000_000),
+        Output::Nanoseconds => duration.as_nanos().to_string(),
+        Output::Iso8601 => format_iso8601(duration),
+        Output::Human => format(duration),
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn json_line(input: &str, result: &Result<Duration, Error>, output: Output) -> String {
+    match result {
+        Ok(duration) => {
+            let value = match output {
+                Output::Iso8601 | Output::Human => json_string(&convert(*duration, output)),
+                _ => convert(*duration, output),
+            };
+            format!("{{\"input\":{},\"value\":{}}}", json_string(input), value)
+        }
+        Err(error) => format!(
+            "{{\"input\":{},\"error\":{},\"message\":{}}}",
+            json_string(input),
+            json_string(error.name()),
+            json_string(&error.to_string())
+        ),
+    }
+}
+
+fn run(arguments: Arguments) -> io::Result<u8> {
+    let options = Options::new().strict(arguments.strict);
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let mut status = 0;
+    let mut convert_one = |input: &str| -> io::Result<()> {
+        let result = parse_with(input, &options);
+        if arguments.json {
+            writeln!(out, "{}", json_line(input, &result, arguments.output))?;
+        } else {
+            match result {
+                Ok(duration) => writeln!(out, "{}", convert(duration, arguments.output))?,
+                Err(ref error) => eprintln!("parse-duration: {}", error),
+            }
+        }
+        if let (0, Err(error)) = (status, &result) {
+            status = exit_code(error);
+        }
+        Ok(())
+    };
+    if arguments.inputs.is_empty() {
+        for line in io::stdin().lock().lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                convert_one(line.trim())?;
+            }
+        }
+    } else {
+        for input in &arguments.inputs {
+            convert_one(input)?;
+        }
+    }
+    Ok(status)
+}
+
+fn main() -> ExitCode {
+    match parse_arguments(env::args().skip(1)) {
+        Ok(Command::Help) => {
+            println!("{}", USAGE);
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Version) => {
+            println!("parse-duration {}", env!("CARGO_PKG_VERSION"));
+            ExitCode::SUCCESS
+        }
+        Ok(Command::Convert(arguments)) => match run(arguments) {
+            Ok(status) => ExitCode::from(status),
+            Err(error) => {
+                eprintln!("parse-duration: {}", error);
+                ExitCode::from(3)
+            }
+        },
+        Err(message) => {
+            eprintln!("parse-duration: {}\n\n{}", message, USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Command, String> {
+        parse_arguments(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn arguments() {
+        assert_eq!(
+            args(&["--to", "ms", "--strict", "1h", "-5s 1m"]),
+            Ok(Command::Convert(Arguments {
+                output: Output::Milliseconds,
+                strict: true,
+                json: false,
+                inputs: vec!["1h".to_owned(), "-5s 1m".to_owned()],
+            }))
+        );
+        assert_eq!(
+            args(&["--to=iso8601", "--json", "--", "--strict"]),
+            Ok(Command::Convert(Arguments {
+                output: Output::Iso8601,
+                strict: false,
+                json: true,
+                inputs: vec!["--strict".to_owned()],
+            }))
+        );
+        assert_eq!(args(&["1h", "--help"]), Ok(Command::Help));
+        assert!(args(&["--to"]).is_err());
+        assert!(args(&["--to", "weeks"]).is_err());
+        assert!(args(&["--verbose"]).is_err());
+    }
+
+    #[test]
+    fn outputs() {
+        let duration = Duration::new(5_400, 250_000_000);
+        assert_eq!(convert(duration, Output::Seconds), "5400.25");
+        assert_eq!(convert(duration, Output::Milliseconds), "5400250");
+        assert_eq!(convert(duration, Output::Nanoseconds), "5400250000000");
+        assert_eq!(convert(duration, Output::Iso8601), "PT1H30M0.25S");
+        assert_eq!(
+            convert(duration, Output::Human),
+            "1 hour 30 minutes and 250 milliseconds"
+        );
+        assert_eq!(
+            convert(Duration::new(0, 1), Output::Milliseconds),
+            "0.000001"
+        );
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            json_line("1m", &Ok(Duration::new(60, 0)), Output::Seconds),
+            r#"{"input":"1m","value":60}"#
+        );
+        assert_eq!(
+            json_line("1m", &Ok(Duration::new(60, 0)), Output::Iso8601),
+            r#"{"input":"1m","value":"PT1M"}"#
+        );
+        assert_eq!(
+            json_line(
+                "1 \"x\"",
+                &Err(Error::UnknownUnit("x".to_owned())),
+                Output::Seconds
+            ),
+            r#"{"input":"1 \"x\"","error":"UnknownUnit","message":"UnknownUnitError: \"x\" is not a known unit"}"#
+        );
+        assert_eq!(
+            Error::TooShort {
+                min: Duration::new(1, 0),
+                value: Duration::new(0, 0)
+            }
+            .name(),
+            "TooShort"
+        );
+    }
+
+    #[test]
+    fn exit_codes() {
+        assert_eq!(exit_code(&Error::UnknownUnit(String::new())), 11);
+        assert_eq!(exit_code(&Error::NoValueFound(String::new())), 16);
+        assert_eq!(exit_code(&Error::UnexpectedToken(String::new())), 18);
+        assert_eq!(exit_code(&Error::TooManyComponents { max: 1 }), 29);
+        assert_eq!(
+            exit_code(&Error::AmbiguousUnit {
+                word: String::new(),
+                candidates: Vec::new()
+            }),
+            30
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! The constant ("c") format of .NET's `TimeSpan`, like `"1.02:03:04.5000000"`.
+//!
+//! See [the .NET documentation](https://learn.microsoft.com/en-us/dotnet/standard/base-types/standard-timespan-format-strings#the-constant-c-format-specifier).
+
+use ::std::time::Duration;
+
+use crate::parse::{Calendar, Error, ProtoDuration, Unit};
+
+/// The number of nanoseconds in a tick, the resolution of a `TimeSpan`.
+const TICK: u32 = 100;
+
+/// The number of ticks in `TimeSpan.MaxValue`, 10675199.02:48:05.4775807.
+const MAX_TICKS: u64 = i64::MAX as u64;
+
+/// The value of 1 to `max_digits` ASCII digits.
+fn digits(text: &str, max_digits: usize) -> Option<u64> {
+    if text.is_empty() || text.len() > max_digits || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+/// Parse a duration like `"1.02:03:04.5000000"` in the "c" format of .NET's `TimeSpan`.
+///
+/// The syntax is `[-][d.]hh:mm:ss[.fffffff]`: optional days, then hours from 0 to 23,
+/// minutes and seconds from 0 to 59, and up to 7 decimals, which are ticks of 100 nanoseconds.
+/// There may be no whitespace.
+///
+/// Durations longer than `TimeSpan.MaxValue` are an [`Error::Overflow`], and negative ones an
+/// [`Error::OutOfBounds`], since `Duration`s are positive. Other invalid inputs are an
+/// [`Error::InvalidFormat`].
+///
+/// [`Error::Overflow`]: ../parse/enum.Error.html#variant.Overflow
+/// [`Error::OutOfBounds`]: ../parse/enum.Error.html#variant.OutOfBounds
+/// [`Error::InvalidFormat`]: ../parse/enum.Error.html#variant.InvalidFormat
+///
+/// ```
+/// use ::parse_duration0::parse_dotnet;
+/// use ::std::time::Duration;
+///
+/// assert_eq!(parse_dotnet("1.02:03:04.5000000"), Ok(Duration::new(93_784, 500_000_000)));
+/// assert_eq!(parse_dotnet("00:30:00"), Ok(Duration::new(1_800, 0)));
+/// ```
+pub fn parse_dotnet(input: &str) -> Result<Duration, Error> {
+    if input.is_empty() {
+        return Err(Error::NoValueFound(String::new()));
+    }
+    let invalid = || Error::InvalidFormat(input.to_owned());
+    let (negative, rest) = match input.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, input),
+    };
+    let mut parts = rest.split(':');
+    let (Some(days_hours), Some(minutes), Some(seconds), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid());
+    };
+    let (days, hours) = match days_hours.split_once('.') {
+        Some((days, hours)) => (digits(days, 8).ok_or_else(invalid)?, hours),
+        None => (0, days_hours),
+    };
+    let (seconds, fraction) = seconds.split_once('.').unwrap_or((seconds, ""));
+    let hours = digits(hours, 2).filter(|&hours| hours < 24);
+    let minutes = digits(minutes, 2).filter(|&minutes| minutes < 60);
+    let seconds = digits(seconds, 2).filter(|&seconds| seconds < 60);
+    let ticks = match fraction {
+        "" if !rest.ends_with('.') => Some(0),
+        // The decimals are a fraction of a second, so ".5" is 5000000 ticks.
+        fraction => digits(fraction, 7).map(|ticks| ticks * 10_u64.pow(7 - fraction.len() as u32)),
+    };
+    let (Some(hours), Some(minutes), Some(seconds), Some(ticks)) = (hours, minutes, seconds, ticks)
+    else {
+        return Err(invalid());
+    };
+    let total_seconds = ((days * 24 + hours) * 60 + minutes) * 60 + seconds;
+    if u128::from(total_seconds) * 10_000_000 + u128::from(ticks) > u128::from(MAX_TICKS) {
+        return Err(Error::Overflow);
+    }
+
+    let sign = if negative { -1 } else { 1 };
+    let mut duration = ProtoDuration::default();
+    for (value, unit) in [
+        (days, Unit::Day),
+        (hours, Unit::Hour),
+        (minutes, Unit::Minute),
+        (seconds, Unit::Second),
+        (ticks * u64::from(TICK), Unit::Nanosecond),
+    ] {
+        // All values are checked above, so they fit.
+        duration.add(unit, sign * value as i64)?;
+    }
+    duration.into_duration(Calendar::Gregorian)
+}
+
+/// Format a duration in the "c" format of .NET's `TimeSpan`, such as `"3.17:25:30.5000000"`.
+///
+/// The duration is rounded down to whole ticks of 100 nanoseconds. Days are left out if there are
+/// none, and the decimals if they are zero. Durations longer than `TimeSpan.MaxValue` are
+/// formatted in the same way, but .NET can not parse them.
+///
+/// ```
+/// use ::parse_duration0::format_dotnet;
+/// use ::std::time::Duration;
+///
+/// assert_eq!(format_dotnet(Duration::new(321_930, 500_000_000)), "3.17:25:30.5000000");
+/// assert_eq!(format_dotnet(Duration::new(1_800, 0)), "00:30:00");
+/// ```
+pub fn format_dotnet(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let ticks = duration.subsec_nanos() / TICK;
+    let mut text = String::new();
+    if seconds >= 86_400 {
+        text.push_str(&format!("{}.", seconds / 86_400));
+    }
+    text.push_str(&format!(
+        "{:02}:{:02}:{:02}",
+        seconds % 86_400 / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60
+    ));
+    if ticks > 0 {
+        text.push_str(&format!(".{:07}", ticks));
+    }
+    text
+}
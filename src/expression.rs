@@ -0,0 +1,431 @@
+use ::std::time::Duration;
+
+use crate::lexer::{Lexer, Token, TokenKind};
+use crate::options::Options;
+use crate::parse::{
+    duration_from_nanoseconds, number_value, Calendar, Error, SpannedError, Unit, Value,
+};
+use crate::words;
+
+/// The maximum number of nested parentheses, to protect the stack.
+const MAX_DEPTH: usize = 64;
+
+#[derive(Debug, Clone, Copy)]
+enum Item {
+    /// A number, possibly followed by a unit.
+    Number(Token),
+    /// A unit, or some other word.
+    Word(Token),
+    /// One of `+ - * / × ÷ ( )`, and where it starts.
+    Operator(char, usize),
+}
+
+impl Item {
+    fn span(&self) -> (usize, usize) {
+        match *self {
+            Item::Number(token) | Item::Word(token) => (token.start, token.end),
+            Item::Operator(c, start) => (start, start + c.len_utf8()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Operand {
+    Scalar(Value),
+    /// A duration in nanoseconds. May be negative while evaluating.
+    Duration(i128),
+}
+
+/// An evaluated part of the expression, and the part of the input it came from.
+#[derive(Debug, Clone, Copy)]
+struct Evaluated {
+    operand: Operand,
+    start: usize,
+    end: usize,
+}
+
+fn error(error: Error, start: usize, end: usize) -> SpannedError {
+    SpannedError::new(error, start..end)
+}
+
+/// Split the input into numbers, words and operators. Anything else except whitespace is an error.
+fn items(input: &str, options: &Options) -> Result<Vec<Item>, SpannedError> {
+    let config = options.parse_config();
+    let lexer = Lexer::new(input, config.lexer);
+    let tokens = if config.natural_language {
+        words::number_words(input, lexer.collect(), &|word| options.resolve(word))
+            .map_err(|error| SpannedError::new(error, 0..input.len()))?
+    } else {
+        lexer.collect()
+    };
+    let mut items = Vec::with_capacity(tokens.len());
+    let mut count = 0;
+    for token in tokens {
+        match token.kind {
+            TokenKind::Number { .. } | TokenKind::WordNumber(_) => {
+                count += 1;
+                if let Some(max) = config.max_components.filter(|&max| count > max) {
+                    let error = Error::TooManyComponents { max };
+                    return Err(SpannedError::new(error, token.start..input.len()));
+                }
+                items.push(Item::Number(token))
+            }
+            TokenKind::Word => items.push(Item::Word(token)),
+            TokenKind::Junk => {
+                for (offset, c) in token.text(input).char_indices() {
+                    let start = token.start + offset;
+                    match c {
+                        '+' | '-' | '*' | '/' | '×' | '÷' | '(' | ')' => {
+                            items.push(Item::Operator(c, start))
+                        }
+                        c if c.is_whitespace() => {}
+                        c => {
+                            return Err(error(
+                                Error::UnexpectedToken(c.to_string()),
+                                start,
+                                start + c.len_utf8(),
+                            ))
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(items)
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    options: &'a Options,
+    items: Vec<Item>,
+    position: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<Item> {
+        self.items.get(self.position).copied()
+    }
+
+    fn unexpected(&self, item: Option<Item>) -> SpannedError {
+        match item {
+            Some(item) => {
+                let (start, end) = item.span();
+                error(
+                    Error::UnexpectedToken(self.input[start..end].to_owned()),
+                    start,
+                    end,
+                )
+            }
+            None => error(Error::UnexpectedEnd, self.input.len(), self.input.len()),
+        }
+    }
+
+    /// The next `+` or `-`, if there is one.
+    /// A negative number directly after a value, as in `"2h -15m"`, is also a subtraction.
+    fn additive_operator(&mut self) -> Option<char> {
+        match self.peek()? {
+            Item::Operator(c @ ('+' | '-'), _) => {
+                self.position += 1;
+                Some(c)
+            }
+            Item::Number(token) if token.text(self.input).starts_with('-') => {
+                // Leave the number without its sign.
+                self.items[self.position] = Item::Number(Token {
+                    start: token.start + 1,
+                    ..token
+                });
+                Some('-')
+            }
+            _ => None,
+        }
+    }
+
+    fn multiplicative_operator(&mut self) -> Option<char> {
+        match self.peek()? {
+            Item::Operator(c @ ('*' | '×' | '/' | '÷'), _) => {
+                self.position += 1;
+                Some(c)
+            }
+            _ => None,
+        }
+    }
+
+    fn expression(&mut self) -> Result<Evaluated, SpannedError> {
+        let mut left = self.term()?;
+        while let Some(operator) = self.additive_operator() {
+            let right = self.term()?;
+            left = self.combine(operator, left, right)?;
+        }
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<Evaluated, SpannedError> {
+        let mut left = self.unary()?;
+        while let Some(operator) = self.multiplicative_operator() {
+            let right = self.unary()?;
+            left = self.combine(operator, left, right)?;
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Evaluated, SpannedError> {
+        match self.peek() {
+            Some(Item::Operator(c @ ('+' | '-'), start)) => {
+                self.position += 1;
+                let mut operand = self.nested(Self::unary)?;
+                operand.start = start;
+                if c == '-' {
+                    operand.operand = match operand.operand {
+                        Operand::Scalar(value) => Operand::Scalar(
+                            value
+                                .checked_mul(Value::integer(-1))
+                                .ok_or_else(|| error(Error::Overflow, start, operand.end))?,
+                        ),
+                        Operand::Duration(nanoseconds) => Operand::Duration(
+                            nanoseconds
+                                .checked_neg()
+                                .ok_or_else(|| error(Error::Overflow, start, operand.end))?,
+                        ),
+                    };
+                }
+                Ok(operand)
+            }
+            _ => self.atom(),
+        }
+    }
+
+    /// Run `parse` one level deeper, failing if that is too deep.
+    fn nested(
+        &mut self,
+        parse: fn(&mut Self) -> Result<Evaluated, SpannedError>,
+    ) -> Result<Evaluated, SpannedError> {
+        if self.depth >= MAX_DEPTH {
+            let (start, end) = self.peek().map_or((0, 0), |item| item.span());
+            return Err(error(Error::NestingTooDeep, start, end));
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn atom(&mut self) -> Result<Evaluated, SpannedError> {
+        match self.peek() {
+            Some(Item::Operator('(', start)) => {
+                self.position += 1;
+                let inner = self.nested(Self::expression)?;
+                match self.peek() {
+                    Some(Item::Operator(')', end)) => {
+                        self.position += 1;
+                        Ok(Evaluated {
+                            start,
+                            end: end + 1,
+                            ..inner
+                        })
+                    }
+                    other => Err(self.unexpected(other)),
+                }
+            }
+            Some(Item::Number(_)) => self.quantity(),
+            other => Err(self.unexpected(other)),
+        }
+    }
+
+    /// A number without a unit, or a sum of values with units like `"1h 30m"`.
+    fn quantity(&mut self) -> Result<Evaluated, SpannedError> {
+        let first = match self.peek() {
+            Some(Item::Number(token)) => token,
+            other => return Err(self.unexpected(other)),
+        };
+        self.position += 1;
+        let value = self.value(first)?;
+        if !matches!(self.peek(), Some(Item::Word(_))) {
+            return Ok(Evaluated {
+                operand: Operand::Scalar(value),
+                start: first.start,
+                end: first.end,
+            });
+        }
+        let mut nanoseconds = 0_i128;
+        let mut number = first;
+        let mut value = value;
+        loop {
+            let Some(Item::Word(unit)) = self.peek() else {
+                unreachable!("only called when a unit follows")
+            };
+            self.position += 1;
+            let word = unit.text(self.input);
+            let resolved = self.options.resolve(word).ok_or_else(|| {
+                let unknown = Error::UnknownUnit(word.to_owned());
+                error(self.options.check_unit(unknown), unit.start, unit.end)
+            })?;
+            nanoseconds = value
+                .nanoseconds(resolved, self.options.parse_config().calendar)
+                .and_then(|component| nanoseconds.checked_add(component))
+                .ok_or_else(|| error(Error::Overflow, number.start, unit.end))?;
+            // Continue with the next value if it has a unit and no sign.
+            match (self.peek(), self.items.get(self.position + 1)) {
+                (Some(Item::Number(next)), Some(Item::Word(_)))
+                    if !next.text(self.input).starts_with('-') =>
+                {
+                    self.position += 1;
+                    number = next;
+                    value = self.value(next)?;
+                }
+                _ => {
+                    return Ok(Evaluated {
+                        operand: Operand::Duration(nanoseconds),
+                        start: first.start,
+                        end: unit.end,
+                    })
+                }
+            }
+        }
+    }
+
+    fn value(&self, number: Token) -> Result<Value, SpannedError> {
+        if let TokenKind::Number { exponent: true, .. } = number.kind {
+            return Err(error(Error::ExpNotSupported, number.start, number.end));
+        }
+        number_value(self.input, number).map_err(|e| error(e, number.start, number.end))
+    }
+
+    fn combine(
+        &self,
+        operator: char,
+        left: Evaluated,
+        right: Evaluated,
+    ) -> Result<Evaluated, SpannedError> {
+        let (start, end) = (left.start, right.end);
+        let invalid =
+            |message: &str| error(Error::InvalidOperation(message.to_owned()), start, end);
+        let overflow = || error(Error::Overflow, start, end);
+        let division_by_zero = || {
+            error(
+                Error::DivisionByZero(self.input[right.start..right.end].to_owned()),
+                right.start,
+                right.end,
+            )
+        };
+        use Operand::{Duration, Scalar};
+        let operand = match (operator, left.operand, right.operand) {
+            ('+', Duration(l), Duration(r)) => Duration(l.checked_add(r).ok_or_else(overflow)?),
+            ('-', Duration(l), Duration(r)) => Duration(l.checked_sub(r).ok_or_else(overflow)?),
+            ('+', Scalar(l), Scalar(r)) => Scalar(l.checked_add(r).ok_or_else(overflow)?),
+            ('-', Scalar(l), Scalar(r)) => Scalar(
+                Value::integer(-1)
+                    .checked_mul(r)
+                    .and_then(|r| l.checked_add(r))
+                    .ok_or_else(overflow)?,
+            ),
+            ('+' | '-', ..) => {
+                return Err(invalid("cannot add or subtract a number and a duration"))
+            }
+            ('*' | '×', Duration(d), Scalar(s)) | ('*' | '×', Scalar(s), Duration(d)) => {
+                Duration(
+                    d.checked_mul(s.numerator)
+                        .and_then(|d| d.checked_div(s.denominator))
+                        .ok_or_else(overflow)?,
+                )
+            }
+            ('*' | '×', Scalar(l), Scalar(r)) => Scalar(l.checked_mul(r).ok_or_else(overflow)?),
+            ('*' | '×', Duration(_), Duration(_)) => {
+                return Err(invalid("cannot multiply two durations"))
+            }
+            (_, Duration(d), Scalar(s)) => {
+                if s.numerator == 0 {
+                    return Err(division_by_zero());
+                }
+                Duration(
+                    d.checked_mul(s.denominator)
+                        .and_then(|d| d.checked_div(s.numerator))
+                        .ok_or_else(overflow)?,
+                )
+            }
+            (_, Duration(l), Duration(r)) => {
+                if r == 0 {
+                    return Err(division_by_zero());
+                }
+                Scalar(Value::ratio(l, r).ok_or_else(overflow)?)
+            }
+            (_, Scalar(l), Scalar(r)) => {
+                if r.numerator == 0 {
+                    return Err(division_by_zero());
+                }
+                Scalar(
+                    Value::ratio(r.denominator, r.numerator)
+                        .and_then(|inverse| l.checked_mul(inverse))
+                        .ok_or_else(overflow)?,
+                )
+            }
+            (_, Scalar(_), Duration(_)) => {
+                return Err(invalid("cannot divide a number by a duration"))
+            }
+        };
+        Ok(Evaluated {
+            operand,
+            start,
+            end,
+        })
+    }
+}
+
+/// Parse and evaluate an arithmetic expression of durations, like `"2h - 15m"`,
+/// `"3 * 45s"` or `"(1d + 2h) / 2"`.
+///
+/// Durations are written as for [`parse`](../parse/fn.parse.html), e.g. `"1h 30m"`,
+/// but words that are not units and characters other than `+ - * / × ÷ ( )` are errors.
+/// Durations can be added and subtracted, and multiplied or divided by numbers.
+/// A duration divided by a duration is a number. If the result is a number, it is in seconds.
+///
+/// Errors contain the byte range of the input that caused them.
+///
+/// ```
+/// use ::parse_duration0::parse_expression;
+/// use ::std::time::Duration;
+///
+/// assert_eq!(parse_expression("2h - 15m"), Ok(Duration::new(6_300, 0)));
+/// assert_eq!(parse_expression("(1d + 2h) / 2"), Ok(Duration::new(46_800, 0)));
+/// assert_eq!(parse_expression("3 * 45s"), Ok(Duration::new(135, 0)));
+///
+/// let error = parse_expression("1h * 2h").unwrap_err();
+/// assert_eq!(error.span, 0..7);
+/// ```
+pub fn parse_expression(input: &str) -> Result<Duration, SpannedError> {
+    parse_expression_with(input, &Options::new())
+}
+
+/// Like [`parse_expression`](fn.parse_expression.html), with the given options.
+///
+/// The bounds of the options apply to the result, not to the values in the expression.
+/// The maximum number of components is the maximum number of values, including scalars.
+pub fn parse_expression_with(input: &str, options: &Options) -> Result<Duration, SpannedError> {
+    options
+        .check_length(input)
+        .map_err(|e| error(e, 0, input.len()))?;
+    let items = items(input, options)?;
+    if items.is_empty() {
+        return Err(error(Error::NoValueFound(input.to_owned()), 0, input.len()));
+    }
+    let mut parser = Parser {
+        input,
+        options,
+        items,
+        position: 0,
+        depth: 0,
+    };
+    let result = parser.expression()?;
+    if let Some(item) = parser.peek() {
+        return Err(parser.unexpected(Some(item)));
+    }
+    let nanoseconds = match result.operand {
+        Operand::Duration(nanoseconds) => Some(nanoseconds),
+        Operand::Scalar(value) => value.nanoseconds(Unit::Second, Calendar::Gregorian),
+    };
+    nanoseconds
+        .ok_or(Error::Overflow)
+        .and_then(duration_from_nanoseconds)
+        .and_then(|value| options.check_bounds(value))
+        .map_err(|e| error(e, result.start, result.end))
+}
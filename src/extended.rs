@@ -0,0 +1,237 @@
+//! An extended mode for scientific use, with units from attoseconds to millennia.
+//!
+//! [`parse`](../parse/fn.parse.html) returns a `Duration`, which has a resolution of nanoseconds
+//! and can not be negative. [`parse_extended`](fn.parse_extended.html) returns an
+//! [`ExtendedDuration`](struct.ExtendedDuration.html) instead, a signed number of attoseconds.
+
+use ::std::time::Duration;
+
+use crate::lexer::{Lexer, LexerConfig, TokenKind};
+use crate::parse::{
+    duration_from_nanoseconds, gcd, lowercase, number_value, parse_unit, Component, Components,
+    Error, LoneInteger, Unit, Value, MAX_UNIT_LEN,
+};
+
+/// The number of attoseconds in a nanosecond.
+const NANOSECOND: i128 = 1_000_000_000;
+
+/// The number of attoseconds in a second.
+const SECOND: i128 = NANOSECOND * 1_000_000_000;
+
+/// A unit of [`parse_extended`](fn.parse_extended.html): the units of
+/// [`parse`](../parse/fn.parse.html), and smaller and larger ones.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ExtendedUnit {
+    Attosecond,
+    Femtosecond,
+    Picosecond,
+    Nanosecond,
+    Microsecond,
+    Millisecond,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+    Fortnight,
+    Quarter,
+    Decade,
+    Century,
+    Jiffy,
+    Kilosecond,
+    Megasecond,
+    Gigasecond,
+    Millennium,
+}
+
+impl ExtendedUnit {
+    /// The number of attoseconds in one of this unit.
+    /// Like in [`parse`](../parse/fn.parse.html), a year is 365.2425 days.
+    pub fn attoseconds(self) -> i128 {
+        match self {
+            ExtendedUnit::Attosecond => 1,
+            ExtendedUnit::Femtosecond => 1_000,
+            ExtendedUnit::Picosecond => 1_000_000,
+            ExtendedUnit::Nanosecond => NANOSECOND,
+            ExtendedUnit::Microsecond => 1_000 * NANOSECOND,
+            ExtendedUnit::Millisecond => 1_000_000 * NANOSECOND,
+            ExtendedUnit::Second => SECOND,
+            ExtendedUnit::Minute => 60 * SECOND,
+            ExtendedUnit::Hour => 3_600 * SECOND,
+            ExtendedUnit::Day => 86_400 * SECOND,
+            ExtendedUnit::Week => 604_800 * SECOND,
+            ExtendedUnit::Month => 2_629_746 * SECOND,
+            ExtendedUnit::Year => 31_556_952 * SECOND,
+            ExtendedUnit::Fortnight => 1_209_600 * SECOND,
+            ExtendedUnit::Quarter => 7_889_238 * SECOND,
+            ExtendedUnit::Decade => 315_569_520 * SECOND,
+            ExtendedUnit::Century => 3_155_695_200 * SECOND,
+            ExtendedUnit::Jiffy => 10_000_000 * NANOSECOND,
+            ExtendedUnit::Kilosecond => 1_000 * SECOND,
+            ExtendedUnit::Megasecond => 1_000_000 * SECOND,
+            ExtendedUnit::Gigasecond => 1_000_000_000 * SECOND,
+            ExtendedUnit::Millennium => 31_556_952_000 * SECOND,
+        }
+    }
+}
+
+impl From<Unit> for ExtendedUnit {
+    fn from(unit: Unit) -> Self {
+        match unit {
+            Unit::Nanosecond => ExtendedUnit::Nanosecond,
+            Unit::Microsecond => ExtendedUnit::Microsecond,
+            Unit::Millisecond => ExtendedUnit::Millisecond,
+            Unit::Second => ExtendedUnit::Second,
+            Unit::Minute => ExtendedUnit::Minute,
+            Unit::Hour => ExtendedUnit::Hour,
+            Unit::Day => ExtendedUnit::Day,
+            Unit::Week => ExtendedUnit::Week,
+            Unit::Month => ExtendedUnit::Month,
+            Unit::Year => ExtendedUnit::Year,
+            Unit::Fortnight => ExtendedUnit::Fortnight,
+            Unit::Quarter => ExtendedUnit::Quarter,
+            Unit::Decade => ExtendedUnit::Decade,
+            Unit::Century => ExtendedUnit::Century,
+            Unit::Jiffy => ExtendedUnit::Jiffy,
+            Unit::Kilosecond => ExtendedUnit::Kilosecond,
+            Unit::Megasecond => ExtendedUnit::Megasecond,
+            Unit::Gigasecond => ExtendedUnit::Gigasecond,
+        }
+    }
+}
+
+/// A duration in attoseconds, which may be negative.
+///
+/// An `i128` of attoseconds covers about 5.4 trillion years either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ExtendedDuration {
+    attoseconds: i128,
+}
+
+impl ExtendedDuration {
+    /// A duration of zero.
+    pub const ZERO: ExtendedDuration = ExtendedDuration { attoseconds: 0 };
+
+    pub const fn from_attoseconds(attoseconds: i128) -> Self {
+        ExtendedDuration { attoseconds }
+    }
+
+    pub const fn as_attoseconds(self) -> i128 {
+        self.attoseconds
+    }
+
+    /// The number of whole picoseconds, rounding towards zero.
+    pub const fn as_picoseconds(self) -> i128 {
+        self.attoseconds / 1_000_000
+    }
+
+    /// The number of whole nanoseconds, rounding towards zero.
+    pub const fn as_nanoseconds(self) -> i128 {
+        self.attoseconds / NANOSECOND
+    }
+
+    pub const fn is_negative(self) -> bool {
+        self.attoseconds < 0
+    }
+
+    /// Convert into a `Duration`, rounding towards zero to whole nanoseconds.
+    ///
+    /// Like [`parse`](../parse/fn.parse.html), this fails with an
+    /// [`Error::OutOfBounds`](../parse/enum.Error.html#variant.OutOfBounds) for negative durations,
+    /// and an [`Error::Overflow`](../parse/enum.Error.html#variant.Overflow) if it is too long.
+    pub fn to_duration(self) -> Result<Duration, Error> {
+        duration_from_nanoseconds(self.as_nanoseconds())
+    }
+}
+
+impl From<Duration> for ExtendedDuration {
+    fn from(duration: Duration) -> Self {
+        ExtendedDuration {
+            attoseconds: i128::from(duration.as_secs()) * SECOND
+                + i128::from(duration.subsec_nanos()) * NANOSECOND,
+        }
+    }
+}
+
+/// Find the unit for a word, like [`parse`](../parse/fn.parse.html) does, or one of the extra units.
+fn extended_unit(word: &str) -> Option<ExtendedUnit> {
+    let mut buffer = [0; MAX_UNIT_LEN];
+    Some(match lowercase(word, &mut buffer)? {
+        "as" | "asec" | "asecs" | "attosecond" | "attoseconds" => ExtendedUnit::Attosecond,
+        "fs" | "fsec" | "fsecs" | "femtosecond" | "femtoseconds" => ExtendedUnit::Femtosecond,
+        "ps" | "psec" | "psecs" | "picosecond" | "picoseconds" => ExtendedUnit::Picosecond,
+        "millennium" | "millennia" | "millenniums" => ExtendedUnit::Millennium,
+        _ => return parse_unit(word).map(ExtendedUnit::from),
+    })
+}
+
+/// `value` times `unit`, in whole attoseconds, rounding towards zero.
+fn attoseconds(value: Value, unit: ExtendedUnit) -> Option<i128> {
+    let unit = unit.attoseconds();
+    let whole = value.numerator / value.denominator;
+    let remainder = value.numerator % value.denominator;
+    // Divide the unit and denominator by their common factors first, which are usually powers
+    // of ten, so that the remainder can be multiplied without overflowing.
+    let divisor = gcd(unit, value.denominator)?;
+    let fraction = remainder.checked_mul(unit / divisor)? / (value.denominator / divisor);
+    whole.checked_mul(unit)?.checked_add(fraction)
+}
+
+/// Parse a string into an [`ExtendedDuration`](struct.ExtendedDuration.html), with attosecond
+/// resolution.
+///
+/// The syntax is that of [`parse`](../parse/fn.parse.html), with extra units:
+///
+/// - attoseconds (`"as"`), femtoseconds (`"fs"`) and picoseconds (`"ps"`)
+/// - millennia, of 1000 years
+///
+/// These are only accepted as full names, in singular or plural, or with the abbreviations above.
+/// Unlike with `parse`, the sum may be negative, and values are exact to the attosecond.
+///
+/// ```
+/// use ::parse_duration0::{parse_extended, ExtendedDuration};
+///
+/// assert_eq!(parse_extended("1.5 ps"), Ok(ExtendedDuration::from_attoseconds(1_500_000)));
+/// assert_eq!(parse_extended("1 ns -250 fs"), Ok(ExtendedDuration::from_attoseconds(999_750_000)));
+/// assert_eq!(parse_extended("3 millennia").unwrap().as_nanoseconds(), 94_670_856_000_000_000_000);
+/// assert!(parse_extended("-1 as").unwrap().is_negative());
+/// ```
+pub fn parse_extended(input: &str) -> Result<ExtendedDuration, Error> {
+    let config = LexerConfig::default();
+    let lone = Lexer::new(input, config).fold(LoneInteger::Possible(None), |lone, token| {
+        lone.observe(input, &token)
+    });
+    if let Some(integer) = lone.integer() {
+        // A lone integer is a number of seconds, which fits since it is at most 64 bits.
+        let seconds = number_value(input, integer)?.numerator;
+        return Ok(ExtendedDuration::from_attoseconds(seconds * SECOND));
+    }
+
+    let components = Components {
+        tokens: Lexer::new(input, config).peekable(),
+    };
+    let mut total: i128 = 0;
+    let mut found_value = false;
+    for Component { number, unit } in components {
+        found_value = true;
+        if let TokenKind::Number { exponent: true, .. } = number.kind {
+            return Err(Error::ExpNotSupported);
+        }
+        let unit = match unit {
+            Some(unit) => unit.text(input),
+            None => return Err(Error::NoUnitFound(number.text(input).to_owned())),
+        };
+        let value = number_value(input, number)?;
+        let resolved = extended_unit(unit).ok_or_else(|| Error::UnknownUnit(unit.to_owned()))?;
+        total = attoseconds(value, resolved)
+            .and_then(|attoseconds| total.checked_add(attoseconds))
+            .ok_or(Error::Overflow)?;
+    }
+    if found_value {
+        Ok(ExtendedDuration::from_attoseconds(total))
+    } else {
+        Err(Error::NoValueFound(input.to_owned()))
+    }
+}
@@ -0,0 +1,178 @@
+//! A C interface to [`parse`](../parse/fn.parse.html).
+//!
+//! The header is `ffi/parse_duration.h`, generated by cbindgen with `cbindgen.toml`.
+//! Regenerate it after changing this module with
+//! `cbindgen --config cbindgen.toml --output ffi/parse_duration.h`.
+
+use ::std::cell::RefCell;
+use ::std::ffi::CString;
+use ::std::ops::Range;
+use ::std::os::raw::c_char;
+use ::std::ptr;
+use ::std::slice;
+use ::std::str;
+
+use crate::parse::{error_span, parse, Error};
+
+/// The result of `pd_parse`. Everything except `Ok` is an error.
+///
+/// The values from 10 up correspond to the variants of `parse::Error`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PdStatus {
+    Ok = 0,
+    /// The input or an output pointer was null.
+    NullPointer = 1,
+    /// The input was not valid UTF-8.
+    InvalidUtf8 = 2,
+    ParseInt = 10,
+    UnknownUnit = 11,
+    OutOfBounds = 12,
+    Overflow = 13,
+    ExpNotSupported = 14,
+    NoUnitFound = 15,
+    NoValueFound = 16,
+    DivisionByZero = 17,
+    UnexpectedToken = 18,
+    UnexpectedEnd = 19,
+    InvalidOperation = 20,
+    NestingTooDeep = 21,
+    InvalidRange = 22,
+    TooShort = 23,
+    TooLong = 24,
+    UnitNotAllowed = 25,
+    ZeroNotAllowed = 26,
+    InvalidFormat = 27,
+    InputTooLong = 28,
+    TooManyComponents = 29,
+    AmbiguousUnit = 30,
+}
+
+impl From<&Error> for PdStatus {
+    fn from(error: &Error) -> Self {
+        match error {
+            Error::ParseInt(_) => PdStatus::ParseInt,
+            Error::UnknownUnit(_) => PdStatus::UnknownUnit,
+            Error::OutOfBounds(_) => PdStatus::OutOfBounds,
+            Error::Overflow => PdStatus::Overflow,
+            Error::ExpNotSupported => PdStatus::ExpNotSupported,
+            Error::NoUnitFound(_) => PdStatus::NoUnitFound,
+            Error::NoValueFound(_) => PdStatus::NoValueFound,
+            Error::DivisionByZero(_) => PdStatus::DivisionByZero,
+            Error::UnexpectedToken(_) => PdStatus::UnexpectedToken,
+            Error::UnexpectedEnd => PdStatus::UnexpectedEnd,
+            Error::InvalidOperation(_) => PdStatus::InvalidOperation,
+            Error::NestingTooDeep => PdStatus::NestingTooDeep,
+            Error::InvalidRange(_) => PdStatus::InvalidRange,
+            Error::TooShort { .. } => PdStatus::TooShort,
+            Error::TooLong { .. } => PdStatus::TooLong,
+            Error::UnitNotAllowed { .. } => PdStatus::UnitNotAllowed,
+            Error::ZeroNotAllowed => PdStatus::ZeroNotAllowed,
+            Error::InvalidFormat(_) => PdStatus::InvalidFormat,
+            Error::InputTooLong { .. } => PdStatus::InputTooLong,
+            Error::TooManyComponents { .. } => PdStatus::TooManyComponents,
+            Error::AmbiguousUnit { .. } => PdStatus::AmbiguousUnit,
+        }
+    }
+}
+
+struct LastError {
+    status: PdStatus,
+    message: CString,
+    span: Range<usize>,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<LastError>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(status: PdStatus, message: String, span: Range<usize>) -> PdStatus {
+    // Messages quote the input, which may contain a nul byte.
+    let message = CString::new(message.replace('\0', "\\0")).unwrap_or_default();
+    LAST_ERROR.with(|last| {
+        *last.borrow_mut() = Some(LastError {
+            status,
+            message,
+            span,
+        })
+    });
+    status
+}
+
+/// Parse the `len` bytes at `input` as a duration, and store it in `secs` and `nanos`.
+///
+/// The input does not need to be nul-terminated. On error, `secs` and `nanos` are not changed,
+/// and `pd_last_error` gives the message.
+///
+/// # Safety
+///
+/// `input` must point to `len` readable bytes, and `secs` and `nanos` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn pd_parse(
+    input: *const c_char,
+    len: usize,
+    secs: *mut u64,
+    nanos: *mut u32,
+) -> PdStatus {
+    if input.is_null() || secs.is_null() || nanos.is_null() {
+        let message = "input, secs and nanos must not be null".to_owned();
+        return set_last_error(PdStatus::NullPointer, message, 0..0);
+    }
+    let bytes = slice::from_raw_parts(input.cast::<u8>(), len);
+    let input = match str::from_utf8(bytes) {
+        Ok(input) => input,
+        Err(error) => {
+            let start = error.valid_up_to();
+            let end = start + error.error_len().unwrap_or(len - start);
+            return set_last_error(PdStatus::InvalidUtf8, error.to_string(), start..end);
+        }
+    };
+    match parse(input) {
+        Ok(duration) => {
+            *secs = duration.as_secs();
+            *nanos = duration.subsec_nanos();
+            LAST_ERROR.with(|last| *last.borrow_mut() = None);
+            PdStatus::Ok
+        }
+        Err(error) => set_last_error(
+            PdStatus::from(&error),
+            error.to_string(),
+            error_span(input, &error),
+        ),
+    }
+}
+
+/// Get the error of the last `pd_parse` call on this thread, and return its status.
+///
+/// `message` is set to a nul-terminated description, which stays valid until the next call of
+/// `pd_parse` on this thread. `span_start` and `span_end` are set to the byte range of the input
+/// that the error is about. If the last call succeeded, the message is null and the span is empty.
+/// Any of the pointers may be null, to leave that value out.
+///
+/// # Safety
+///
+/// The pointers must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn pd_last_error(
+    message: *mut *const c_char,
+    span_start: *mut usize,
+    span_end: *mut usize,
+) -> PdStatus {
+    LAST_ERROR.with(|last| {
+        let last = last.borrow();
+        let (status, text, span) = match *last {
+            Some(ref error) => (error.status, error.message.as_ptr(), error.span.clone()),
+            None => (PdStatus::Ok, ptr::null(), 0..0),
+        };
+        if !message.is_null() {
+            *message = text;
+        }
+        if !span_start.is_null() {
+            *span_start = span.start;
+        }
+        if !span_end.is_null() {
+            *span_end = span.end;
+        }
+        status
+    })
+}
@@ -0,0 +1,136 @@
+use ::std::iter::Peekable;
+use ::std::ops::Range;
+use ::std::time::Duration;
+
+use crate::lexer::{Lexer, LexerConfig, Token, TokenKind};
+use crate::parse::{parse, parse_unit};
+
+/// A duration in a text, with its byte range, see [`find_durations`](fn.find_durations.html).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoundDuration {
+    pub span: Range<usize>,
+    pub duration: Duration,
+}
+
+/// An iterator over the durations in a text, see [`find_durations`](fn.find_durations.html).
+#[derive(Debug)]
+pub struct FindDurations<'a> {
+    text: &'a str,
+    tokens: Peekable<Lexer<'a>>,
+    /// The end of the previous duration.
+    end: usize,
+}
+
+/// Find each duration in a text, like the three in
+/// `"took 3.5s, retried after 200ms, timed out at 30 s"`.
+///
+/// Unlike [`parse`](../parse/fn.parse.html), which sums all values in its input, this splits
+/// the values into separate durations. Values with units belong to the same duration if only
+/// whitespace or the word "and" is between them, like `"1 hour and 30 minutes"` or `"1h30m"`.
+/// Anything else splits them: punctuation like commas and periods, other words, and numbers
+/// without a unit. A sign directly after a unit also splits them, so `"3s-5s"` is two durations.
+///
+/// Numbers without a unit are not durations, and durations that `parse` rejects, like
+/// `"1e5 s"` or `"-1 h"`, are skipped.
+///
+/// ```
+/// use ::parse_duration0::find_durations;
+/// use ::std::time::Duration;
+///
+/// let text = "took 3.5s, retried after 200ms, timed out at 1 min 30 s";
+/// let found: Vec<_> = find_durations(text).collect();
+/// assert_eq!(found.len(), 3);
+/// assert_eq!(found[1].duration, Duration::new(0, 200_000_000));
+/// assert_eq!(&text[found[2].span.clone()], "1 min 30 s");
+/// assert_eq!(found[2].duration, Duration::new(90, 0));
+/// ```
+pub fn find_durations(text: &str) -> FindDurations<'_> {
+    FindDurations {
+        text,
+        tokens: Lexer::new(text, LexerConfig::default()).peekable(),
+        end: 0,
+    }
+}
+
+fn is_whitespace(text: &str) -> bool {
+    text.chars().all(char::is_whitespace)
+}
+
+impl FindDurations<'_> {
+    /// Read a number and the unit after it, returning the end of the unit.
+    /// If there is no unit, only the number and any whitespace after it are read.
+    fn component(&mut self) -> Option<usize> {
+        self.tokens.next();
+        if let Some(token) = self.tokens.peek() {
+            if token.kind == TokenKind::Junk && is_whitespace(token.text(self.text)) {
+                self.tokens.next();
+            }
+        }
+        let unit = self.tokens.peek()?;
+        if unit.kind == TokenKind::Word && parse_unit(unit.text(self.text)).is_some() {
+            let end = unit.end;
+            self.tokens.next();
+            Some(end)
+        } else {
+            None
+        }
+    }
+
+    /// Whether `token` ends the duration in `span`, without being part of it.
+    fn splits(&self, span: &Range<usize>, token: &Token) -> bool {
+        let text = token.text(self.text);
+        match token.kind {
+            TokenKind::Number { .. } => token.start == span.end && text.starts_with('-'),
+            TokenKind::Word => !text.eq_ignore_ascii_case("and"),
+            TokenKind::Junk => !is_whitespace(text),
+            TokenKind::WordNumber(_) => true,
+        }
+    }
+
+    /// The span of the next group of values with units, if there is one.
+    fn group(&mut self) -> Option<Range<usize>> {
+        let mut span: Option<Range<usize>> = None;
+        while let Some(&token) = self.tokens.peek() {
+            if let Some(span) = &span {
+                if self.splits(span, &token) {
+                    break;
+                }
+            }
+            if let TokenKind::Number { .. } = token.kind {
+                // The sign that split "3s-5s" is a dash, not part of the second duration.
+                let dash = token.start > 0 && token.start == self.end;
+                let start = if dash && token.text(self.text).starts_with('-') {
+                    token.start + 1
+                } else {
+                    token.start
+                };
+                match (self.component(), &mut span) {
+                    (Some(end), Some(span)) => span.end = end,
+                    (Some(end), None) => span = Some(start..end),
+                    (None, Some(_)) => break,
+                    (None, None) => {}
+                }
+            } else {
+                self.tokens.next();
+            }
+        }
+        // Whitespace and "and" after the last unit are not part of the duration.
+        if let Some(span) = &span {
+            self.end = span.end;
+        }
+        span
+    }
+}
+
+impl Iterator for FindDurations<'_> {
+    type Item = FoundDuration;
+
+    fn next(&mut self) -> Option<FoundDuration> {
+        loop {
+            let span = self.group()?;
+            if let Ok(duration) = parse(&self.text[span.clone()]) {
+                return Some(FoundDuration { span, duration });
+            }
+        }
+    }
+}
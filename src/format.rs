@@ -0,0 +1,110 @@
+use ::std::time::Duration;
+
+use crate::locale::{Locale, EN};
+use crate::parse::Unit;
+
+/// Split a duration into whole days, hours, minutes, seconds, milliseconds, microseconds and
+/// nanoseconds, leaving out zero components.
+///
+/// Weeks, months and years are not used, since months and years are averages and would make
+/// the output hard to read.
+fn components(duration: Duration) -> Vec<(u64, Unit)> {
+    let seconds = duration.as_secs();
+    let nanoseconds = u64::from(duration.subsec_nanos());
+    [
+        (seconds / 86_400, Unit::Day),
+        (seconds % 86_400 / 3_600, Unit::Hour),
+        (seconds % 3_600 / 60, Unit::Minute),
+        (seconds % 60, Unit::Second),
+        (nanoseconds / 1_000_000, Unit::Millisecond),
+        (nanoseconds % 1_000_000 / 1_000, Unit::Microsecond),
+        (nanoseconds % 1_000, Unit::Nanosecond),
+    ]
+    .iter()
+    .copied()
+    .filter(|&(value, _)| value != 0)
+    .collect()
+}
+
+/// Format a duration as English text, such as "1 hour 15 minutes and 29 seconds".
+///
+/// The result can be read back by [`parse`](../parse/fn.parse.html).
+///
+/// ```
+/// use ::parse_duration0::format;
+/// use ::std::time::Duration;
+///
+/// assert_eq!(format(Duration::new(4_529, 0)), "1 hour 15 minutes and 29 seconds");
+/// ```
+pub fn format(duration: Duration) -> String {
+    format_localized(&EN, duration)
+}
+
+/// Format a duration as text in the language of `locale`.
+///
+/// The result can be read back by [`parse_localized`](../locale/fn.parse_localized.html)
+/// with the same locale.
+pub fn format_localized(locale: &Locale, duration: Duration) -> String {
+    let components = components(duration);
+    if components.is_empty() {
+        return format_component(locale, 0, Unit::Second);
+    }
+    let mut text = String::new();
+    for (index, &(value, unit)) in components.iter().enumerate() {
+        if index + 1 == components.len() && index > 0 {
+            text.push_str(locale.last_separator());
+        } else if index > 0 {
+            text.push_str(locale.component_separator());
+        }
+        text.push_str(&format_component(locale, value, unit));
+    }
+    text
+}
+
+/// Format a duration in the ISO 8601 duration format, such as "P1DT2H30M" or "PT0.5S".
+///
+/// Days are the largest unit, since months and years have different lengths.
+///
+/// ```
+/// use ::parse_duration0::format_iso8601;
+/// use ::std::time::Duration;
+///
+/// assert_eq!(format_iso8601(Duration::new(95_400, 0)), "P1DT2H30M");
+/// assert_eq!(format_iso8601(Duration::new(1, 500_000_000)), "PT1.5S");
+/// ```
+pub fn format_iso8601(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let nanoseconds = duration.subsec_nanos();
+    let mut text = String::from("P");
+    let days = seconds / 86_400;
+    if days > 0 {
+        text.push_str(&format!("{}D", days));
+    }
+    let (hours, minutes, seconds) = (seconds % 86_400 / 3_600, seconds % 3_600 / 60, seconds % 60);
+    if hours == 0 && minutes == 0 && seconds == 0 && nanoseconds == 0 && days > 0 {
+        return text;
+    }
+    text.push('T');
+    if hours > 0 {
+        text.push_str(&format!("{}H", hours));
+    }
+    if minutes > 0 {
+        text.push_str(&format!("{}M", minutes));
+    }
+    if nanoseconds > 0 {
+        let fraction = format!("{:09}", nanoseconds);
+        text.push_str(&format!("{}.{}S", seconds, fraction.trim_end_matches('0')));
+    } else if seconds > 0 || text == "PT" {
+        text.push_str(&format!("{}S", seconds));
+    }
+    text
+}
+
+fn format_component(locale: &Locale, value: u64, unit: Unit) -> String {
+    format!(
+        "{}{}{}",
+        value,
+        locale.value_separator(),
+        locale.unit_name(unit, value)
+    )
+}
@@ -0,0 +1,282 @@
+//! The duration syntax of Go, as in Kubernetes manifests and Docker options like `--health-interval`.
+//!
+//! This follows [`time.ParseDuration`](https://pkg.go.dev/time#ParseDuration), with optional
+//! extra units like `"d"` that Go itself does not know.
+
+use ::std::ops::Range;
+use ::std::time::Duration;
+
+use crate::lexer::{Lexer, LexerConfig, Token, TokenKind};
+use crate::parse::{duration_from_nanoseconds, Error, SpannedError, Unit};
+
+/// The number of fractional digits that are used. More digits cannot change the nanoseconds
+/// of a value, unless an extension unit is longer than about 30 years.
+const MAX_FRACTION_DIGITS: usize = 18;
+
+/// The most nanoseconds that a Go duration can have, which is `-2^63` when negative.
+const MAX_NANOSECONDS: i128 = 1 << 63;
+
+/// Units that [`parse_go_with`](fn.parse_go_with.html) accepts besides those of Go.
+///
+/// There are no extensions by default, so that the options accept exactly what Go accepts.
+/// The units of Go always take precedence over extensions with the same name.
+///
+/// ```
+/// use ::parse_duration0::{parse_go_with, GoOptions, Unit};
+/// use ::std::time::Duration;
+///
+/// let options = GoOptions::new().extension("d", Unit::Day).extension("w", Unit::Week);
+/// assert_eq!(parse_go_with("1d12h", &options), Ok(Duration::new(129_600, 0)));
+/// assert_eq!(parse_go_with("2w", &options), Ok(Duration::new(1_209_600, 0)));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GoOptions {
+    extensions: Vec<(String, Unit)>,
+}
+
+impl GoOptions {
+    /// The default options, without extensions.
+    pub fn new() -> Self {
+        GoOptions::default()
+    }
+
+    /// Also accept `word` as a unit, like `"d"` for days. Like the units of Go, it is case-sensitive.
+    pub fn extension(mut self, word: &str, unit: Unit) -> Self {
+        self.extensions.push((word.to_owned(), unit));
+        self
+    }
+
+    fn resolve_extension(&self, word: &str) -> Option<Unit> {
+        self.extensions
+            .iter()
+            .find(|(extension, _)| extension == word)
+            .map(|&(_, unit)| unit)
+    }
+}
+
+/// The units that Go accepts.
+fn go_unit(word: &str) -> Option<Unit> {
+    Some(match word {
+        "ns" => Unit::Nanosecond,
+        // Both the micro sign and the Greek letter mu.
+        "us" | "\u{b5}s" | "\u{3bc}s" => Unit::Microsecond,
+        "ms" => Unit::Millisecond,
+        "s" => Unit::Second,
+        "m" => Unit::Minute,
+        "h" => Unit::Hour,
+        _ => return None,
+    })
+}
+
+/// The integer and fractional digits of a number without a sign, exponent or fraction like `1/2`.
+fn plain_digits<'a>(input: &'a str, number: &Token) -> Option<(&'a str, &'a str)> {
+    match number.kind {
+        TokenKind::Number {
+            int_end,
+            fraction,
+            numerator: None,
+            denominator: None,
+            vulgar: None,
+            exponent: false,
+        } if !number.text(input).starts_with('-') => {
+            let fraction = fraction.map_or("", |(start, end)| &input[start..end]);
+            Some((&input[number.start..int_end], fraction))
+        }
+        _ => None,
+    }
+}
+
+/// Move a token of `&input[offset..]` to its place in `input`.
+fn shift(token: Token, offset: usize) -> Token {
+    let range = |(start, end): (usize, usize)| (start + offset, end + offset);
+    let kind = match token.kind {
+        TokenKind::Number {
+            int_end,
+            fraction,
+            numerator,
+            denominator,
+            vulgar,
+            exponent,
+        } => TokenKind::Number {
+            int_end: int_end + offset,
+            fraction: fraction.map(range),
+            numerator: numerator.map(range),
+            denominator: denominator.map(range),
+            vulgar,
+            exponent,
+        },
+        kind => kind,
+    };
+    Token {
+        kind,
+        start: token.start + offset,
+        end: token.end + offset,
+    }
+}
+
+/// The number of nanoseconds in a Go duration, which may be negative,
+/// and the uses of extension units as the errors that Go would give for them.
+fn go_nanoseconds(
+    input: &str,
+    options: &GoOptions,
+) -> Result<(i128, Vec<SpannedError>), SpannedError> {
+    let invalid =
+        |span: Range<usize>| SpannedError::new(Error::InvalidFormat(input.to_owned()), span);
+    let (negative, offset) = match input.as_bytes().first() {
+        Some(b'-') => (true, 1),
+        Some(b'+') => (false, 1),
+        _ => (false, 0),
+    };
+    match &input[offset..] {
+        "0" => return Ok((0, Vec::new())),
+        "" if offset == 0 => {
+            return Err(SpannedError::new(Error::NoValueFound(String::new()), 0..0))
+        }
+        "" => return Err(invalid(0..offset)),
+        _ => {}
+    }
+    let config = LexerConfig {
+        unicode_words: true,
+        ..LexerConfig::default()
+    };
+    let mut tokens = Lexer::new(&input[offset..], config).map(|token| shift(token, offset));
+    let mut total: i128 = 0;
+    let mut extensions = Vec::new();
+    while let Some(token) = tokens.next() {
+        // The lexer does not read a value like ".5" as a number, but Go does.
+        let (start, (whole, fraction)) = if token.text(input) == "." {
+            match tokens.next() {
+                Some(number) => match plain_digits(input, &number) {
+                    Some((digits, "")) if digits.len() == number.text(input).len() => {
+                        (token.start, ("", digits))
+                    }
+                    _ => return Err(invalid(number.start..number.end)),
+                },
+                None => return Err(invalid(token.start..token.end)),
+            }
+        } else {
+            match plain_digits(input, &token) {
+                Some(digits) => (token.start, digits),
+                None => return Err(invalid(token.start..token.end)),
+            }
+        };
+        let unit = match tokens.next() {
+            Some(unit) if unit.kind == TokenKind::Word => unit,
+            Some(other) => return Err(invalid(other.start..other.end)),
+            None => {
+                let number = input[start..].to_owned();
+                return Err(SpannedError::new(
+                    Error::NoUnitFound(number),
+                    start..input.len(),
+                ));
+            }
+        };
+        let word = unit.text(input);
+        let resolved = match (go_unit(word), options.resolve_extension(word)) {
+            (Some(resolved), _) => resolved,
+            (None, Some(resolved)) => {
+                let error = Error::UnknownUnit(word.to_owned());
+                extensions.push(SpannedError::new(error, unit.start..unit.end));
+                resolved
+            }
+            (None, None) => {
+                let error = Error::UnknownUnit(word.to_owned());
+                return Err(SpannedError::new(error, unit.start..unit.end));
+            }
+        };
+        let overflow = || SpannedError::new(Error::Overflow, start..unit.end);
+        let whole: i128 = match whole {
+            "" => 0,
+            digits => digits.parse().map_err(|_| overflow())?,
+        };
+        let fraction = &fraction[..fraction.len().min(MAX_FRACTION_DIGITS)];
+        let scale = 10_i128.pow(fraction.len() as u32);
+        let fraction: i128 = match fraction {
+            "" => 0,
+            digits => digits.parse().map_err(|_| overflow())?,
+        };
+        let unit_nanoseconds = i128::from(resolved.nanoseconds());
+        let nanoseconds = whole
+            .checked_mul(unit_nanoseconds)
+            .map(|nanoseconds| nanoseconds + fraction * unit_nanoseconds / scale)
+            .filter(|&nanoseconds| nanoseconds <= MAX_NANOSECONDS)
+            .ok_or_else(overflow)?;
+        total += nanoseconds;
+        if total > MAX_NANOSECONDS {
+            return Err(overflow());
+        }
+    }
+    if negative {
+        Ok((-total, extensions))
+    } else if total < MAX_NANOSECONDS {
+        Ok((total, extensions))
+    } else {
+        Err(SpannedError::new(Error::Overflow, 0..input.len()))
+    }
+}
+
+/// Parse a duration like `"1h30m"` or `"1.5s"` the way Go does.
+///
+/// This is the same as [`parse_go_with`](fn.parse_go_with.html) with the default options,
+/// so it accepts exactly what Kubernetes and Docker accept.
+///
+/// ```
+/// use ::parse_duration0::{parse_go, Error};
+/// use ::std::time::Duration;
+///
+/// assert_eq!(parse_go("1h30m"), Ok(Duration::new(5_400, 0)));
+/// assert_eq!(parse_go("1.5s"), Ok(Duration::new(1, 500_000_000)));
+/// assert_eq!(parse_go("1d"), Err(Error::UnknownUnit("d".to_string())));
+/// ```
+pub fn parse_go(input: &str) -> Result<Duration, Error> {
+    parse_go_with(input, &GoOptions::new())
+}
+
+/// Parse a duration the way Go does, with the extension units of `options`.
+///
+/// The syntax is an optional sign, followed by values with a unit, without spaces.
+/// Values may have a fractional part like `"1.5h"` or `".5h"`, and units may occur in any order
+/// and more than once. The units are `ns`, `us` (or `µs`), `ms`, `s`, `m` and `h`.
+/// Only `"0"` may be written without a unit.
+///
+/// Compared to Go, there are a few differences:
+///
+/// * Fractional values are rounded down to the nanosecond exactly, where Go uses floating point
+///   numbers and may differ by a nanosecond.
+/// * Negative durations are valid in Go, but they are an
+///   [`Error::OutOfBounds`](../parse/enum.Error.html#variant.OutOfBounds) here, as with
+///   [`parse`](../parse/fn.parse.html).
+///
+/// Other invalid inputs are an [`Error::InvalidFormat`](../parse/enum.Error.html#variant.InvalidFormat),
+/// except for unknown units, values without a unit, an empty input and durations of
+/// 2<sup>63</sup> nanoseconds or more, which have their own error.
+pub fn parse_go_with(input: &str, options: &GoOptions) -> Result<Duration, Error> {
+    let (nanoseconds, _) = go_nanoseconds(input, options).map_err(|error| error.error)?;
+    duration_from_nanoseconds(nanoseconds)
+}
+
+/// Find out why Go, and so Kubernetes and Docker, would reject `input`.
+///
+/// This returns nothing if Go accepts the input. If `input` is only valid because of the
+/// extension units of `options`, each of those is an [`Error::UnknownUnit`], which is what Go
+/// reports. Otherwise, it returns the error of [`parse_go_with`](fn.parse_go_with.html).
+/// Every error has the byte range of the input that it is about.
+///
+/// [`Error::UnknownUnit`]: ../parse/enum.Error.html#variant.UnknownUnit
+///
+/// ```
+/// use ::parse_duration0::{lint_go, Error, GoOptions, SpannedError, Unit};
+///
+/// let options = GoOptions::new().extension("d", Unit::Day);
+/// assert_eq!(lint_go("36h", &options), vec![]);
+/// assert_eq!(
+///     lint_go("1d12h", &options),
+///     vec![SpannedError { error: Error::UnknownUnit("d".to_string()), span: 1..2 }]
+/// );
+/// ```
+pub fn lint_go(input: &str, options: &GoOptions) -> Vec<SpannedError> {
+    match go_nanoseconds(input, options) {
+        Ok((_, extensions)) => extensions,
+        Err(error) => vec![error],
+    }
+}
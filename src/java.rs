@@ -0,0 +1,159 @@
+//! The duration syntax of Java, as in `java.time.Duration.parse` and `Duration.toString`.
+//!
+//! See [the Java documentation](https://docs.oracle.com/en/java/javase/21/docs/api/java.base/java/time/Duration.html#parse(java.lang.CharSequence)).
+
+use ::std::time::Duration;
+
+use crate::parse::{Calendar, Error, ProtoDuration, Unit};
+
+/// Split a number like `"-12"` or `"12.5"` off `text` if it is followed by `designator`,
+/// and remove both from `text`. Only seconds may have a fraction.
+fn designated<'a>(text: &mut &'a str, designator: u8, fraction: bool) -> Option<&'a str> {
+    let bytes = text.as_bytes();
+    let mut end = usize::from(matches!(bytes.first(), Some(b'-' | b'+')));
+    let digits = end;
+    while bytes.get(end).is_some_and(u8::is_ascii_digit) {
+        end += 1;
+    }
+    if end == digits {
+        return None;
+    }
+    if fraction && matches!(bytes.get(end), Some(b'.' | b',')) {
+        end += 1;
+        while bytes.get(end).is_some_and(u8::is_ascii_digit) {
+            end += 1;
+        }
+    }
+    if !bytes.get(end)?.eq_ignore_ascii_case(&designator) {
+        return None;
+    }
+    let number = &text[..end];
+    *text = &text[end + 1..];
+    Some(number)
+}
+
+/// The number of seconds of a component, which must fit in an `i64` as in Java.
+fn component(number: &str, seconds: i64) -> Result<i64, Error> {
+    let value: i64 = number.parse().map_err(|_| Error::Overflow)?;
+    value.checked_mul(seconds).ok_or(Error::Overflow)
+}
+
+/// Parse a duration like `"PT8H6M12.345S"` the way Java's `Duration.parse` does.
+///
+/// The syntax is `PnDTnHnMn.nS`, where each of the components is optional, but there must be at
+/// least one, and `T` must be left out if there are no hours, minutes and seconds.
+/// Letters may be lower case, and seconds may have up to 9 decimals after a `.` or `,`.
+///
+/// Each component and the whole duration may have a sign. A day is always 24 hours.
+/// The sum must not be negative, since `Duration`s are positive, so `"-PT-6H+3M"` is 5 hours and
+/// 57 minutes, but `"-PT6H"` is an [`Error::OutOfBounds`](../parse/enum.Error.html#variant.OutOfBounds).
+/// Other invalid inputs are an [`Error::InvalidFormat`](../parse/enum.Error.html#variant.InvalidFormat).
+///
+/// ```
+/// use ::parse_duration0::parse_java;
+/// use ::std::time::Duration;
+///
+/// assert_eq!(parse_java("PT8H6M12.345S"), Ok(Duration::new(29_172, 345_000_000)));
+/// assert_eq!(parse_java("P2DT3H4M"), Ok(Duration::new(183_840, 0)));
+/// assert_eq!(parse_java("-PT-6H+3M"), Ok(Duration::new(21_420, 0)));
+/// ```
+pub fn parse_java(input: &str) -> Result<Duration, Error> {
+    if input.is_empty() {
+        return Err(Error::NoValueFound(String::new()));
+    }
+    let invalid = || Error::InvalidFormat(input.to_owned());
+    let (negative, rest) = match input.as_bytes()[0] {
+        b'-' => (true, &input[1..]),
+        b'+' => (false, &input[1..]),
+        _ => (false, input),
+    };
+    let mut rest = rest.strip_prefix(['P', 'p']).ok_or_else(invalid)?;
+    let days = designated(&mut rest, b'D', false);
+    let (mut hours, mut minutes, mut seconds) = (None, None, None);
+    if let Some(time) = rest.strip_prefix(['T', 't']) {
+        rest = time;
+        hours = designated(&mut rest, b'H', false);
+        minutes = designated(&mut rest, b'M', false);
+        seconds = designated(&mut rest, b'S', true);
+        if hours.is_none() && minutes.is_none() && seconds.is_none() {
+            return Err(invalid());
+        }
+    } else if days.is_none() {
+        return Err(invalid());
+    }
+    if !rest.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut total = 0;
+    let mut nanoseconds = 0;
+    if let Some(number) = seconds {
+        let (whole, fraction) = match number.find(['.', ',']) {
+            Some(separator) => (&number[..separator], &number[separator + 1..]),
+            None => (number, ""),
+        };
+        if fraction.len() > 9 {
+            return Err(invalid());
+        }
+        total = component(whole, 1)?;
+        if !fraction.is_empty() {
+            // The fraction has the sign of the seconds, so "PT-0.5S" is half a second back.
+            nanoseconds = format!("{:0<9}", fraction).parse().map_err(|_| invalid())?;
+            if whole.starts_with('-') {
+                nanoseconds = -nanoseconds;
+            }
+        }
+    }
+    // Like Java, the seconds of the components must add up to an `i64`, added from the right.
+    for (number, unit_seconds) in [(minutes, 60), (hours, 3_600), (days, 86_400)] {
+        if let Some(number) = number {
+            total = component(number, unit_seconds)?
+                .checked_add(total)
+                .ok_or(Error::Overflow)?;
+        }
+    }
+    if negative {
+        total = total.checked_neg().ok_or(Error::Overflow)?;
+        nanoseconds = -nanoseconds;
+    }
+    let mut duration = ProtoDuration::default();
+    duration.add(Unit::Second, total)?;
+    duration.add(Unit::Nanosecond, nanoseconds)?;
+    duration.into_duration(Calendar::Gregorian)
+}
+
+/// Format a duration the way Java's `Duration.toString` does, such as `"PT8H6M12.345S"`.
+///
+/// Unlike [`format_iso8601`](../format/fn.format_iso8601.html), there are no days,
+/// so a duration of two days is `"PT48H"`. Zero is `"PT0S"`.
+///
+/// ```
+/// use ::parse_duration0::format_java;
+/// use ::std::time::Duration;
+///
+/// assert_eq!(format_java(Duration::new(29_172, 345_000_000)), "PT8H6M12.345S");
+/// assert_eq!(format_java(Duration::new(172_800, 0)), "PT48H");
+/// assert_eq!(format_java(Duration::new(0, 0)), "PT0S");
+/// ```
+pub fn format_java(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let nanoseconds = duration.subsec_nanos();
+    if seconds == 0 && nanoseconds == 0 {
+        return "PT0S".to_owned();
+    }
+    let mut text = String::from("PT");
+    let (hours, minutes, seconds) = (seconds / 3_600, seconds % 3_600 / 60, seconds % 60);
+    if hours > 0 {
+        text.push_str(&format!("{}H", hours));
+    }
+    if minutes > 0 {
+        text.push_str(&format!("{}M", minutes));
+    }
+    if nanoseconds > 0 {
+        let fraction = format!("{:09}", nanoseconds);
+        text.push_str(&format!("{}.{}S", seconds, fraction.trim_end_matches('0')));
+    } else if seconds > 0 {
+        text.push_str(&format!("{}S", seconds));
+    }
+    text
+}
@@ -0,0 +1,271 @@
+//! The lexer splits the input into numbers, words and junk (everything else).
+//!
+//! It follows the grammar of the regular expressions that the parser originally used:
+//! a number is an optional `-`, digits, an optional decimal separator with optional digits and an
+//! optional exponent. A word is a run of word characters without digits.
+//! Numbers may also be fractions, like `1/2`, `½` or `1½`, or mixed numbers like `1 1/2`.
+
+use crate::parse::Value;
+
+/// How the lexer recognizes numbers and words.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LexerConfig<'a> {
+    /// The character between the integer and the fractional part of a number.
+    pub decimal_separator: char,
+    /// Characters that may appear between two digits to group them, e.g. `_` in `1_000`.
+    pub group_separators: &'a [char],
+    /// Whether non-ASCII characters, like the ones in "días" or "分", are word characters.
+    /// If not, they are junk.
+    pub unicode_words: bool,
+}
+
+impl Default for LexerConfig<'_> {
+    fn default() -> Self {
+        LexerConfig {
+            decimal_separator: '.',
+            group_separators: &[],
+            unicode_words: false,
+        }
+    }
+}
+
+impl LexerConfig<'_> {
+    fn is_word_char(&self, c: char) -> bool {
+        if c.is_ascii() {
+            c.is_ascii_alphabetic() || c == '_'
+        } else {
+            self.unicode_words
+                && vulgar_fraction(c).is_none()
+                && !matches!(c, '\u{a0}' | '\u{3000}'..='\u{3002}' | '\u{ff0c}')
+        }
+    }
+}
+
+/// The numerator and denominator of a Unicode vulgar fraction character like '½'.
+pub(crate) fn vulgar_fraction(c: char) -> Option<(u8, u8)> {
+    Some(match c {
+        '½' => (1, 2),
+        '⅓' => (1, 3),
+        '⅔' => (2, 3),
+        '¼' => (1, 4),
+        '¾' => (3, 4),
+        '⅕' => (1, 5),
+        '⅖' => (2, 5),
+        '⅗' => (3, 5),
+        '⅘' => (4, 5),
+        '⅙' => (1, 6),
+        '⅚' => (5, 6),
+        '⅐' => (1, 7),
+        '⅛' => (1, 8),
+        '⅜' => (3, 8),
+        '⅝' => (5, 8),
+        '⅞' => (7, 8),
+        '⅑' => (1, 9),
+        '⅒' => (1, 10),
+        '↉' => (0, 3),
+        _ => return None,
+    })
+}
+
+/// The ASCII slash, or the Unicode fraction slash as in "1⁄2".
+fn is_fraction_slash(c: char) -> bool {
+    c == '/' || c == '\u{2044}'
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TokenKind {
+    /// A number. The integer part, including the sign, ends at `int_end`.
+    /// `fraction` is the range of the digits after the decimal separator, if there are any.
+    /// `denominator` is the range of the digits after the slash of a fraction like `1/2`,
+    /// and `vulgar` is the value of a character like '½'. The integer part may be empty for these.
+    /// In a mixed number like `1 1/2`, the integer part is the whole number and `numerator` is the
+    /// range of the digits before the slash.
+    Number {
+        int_end: usize,
+        fraction: Option<(usize, usize)>,
+        numerator: Option<(usize, usize)>,
+        denominator: Option<(usize, usize)>,
+        vulgar: Option<(u8, u8)>,
+        exponent: bool,
+    },
+    /// A value written in words, like "twenty five" or "half an".
+    /// These are only produced by the `words` module, not by the lexer.
+    WordNumber(Value),
+    Word,
+    Junk,
+}
+
+/// A token, with its byte range in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Token {
+    pub kind: TokenKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Token {
+    pub fn text<'a>(&self, input: &'a str) -> &'a str {
+        &input[self.start..self.end]
+    }
+}
+
+/// An iterator over the tokens of an input.
+#[derive(Debug)]
+pub(crate) struct Lexer<'a> {
+    input: &'a str,
+    config: LexerConfig<'a>,
+    position: usize,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a str, config: LexerConfig<'a>) -> Self {
+        Lexer {
+            input,
+            config,
+            position: 0,
+        }
+    }
+
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.input[self.position..].chars().nth(offset)
+    }
+
+    fn peek_digit(&self, offset: usize) -> bool {
+        self.peek(offset).is_some_and(|c| c.is_ascii_digit())
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek(0) {
+            self.position += c.len_utf8();
+        }
+    }
+
+    fn peek_vulgar(&self, offset: usize) -> Option<(u8, u8)> {
+        self.peek(offset).and_then(vulgar_fraction)
+    }
+
+    fn starts_number(&self) -> bool {
+        match self.peek(0) {
+            Some('-') => self.peek_digit(1) || self.peek_vulgar(1).is_some(),
+            Some(c) => c.is_ascii_digit() || vulgar_fraction(c).is_some(),
+            None => false,
+        }
+    }
+
+    /// Whether a fraction like `1/2` starts `offset` bytes after the current position.
+    fn fraction_follows(&self, offset: usize) -> bool {
+        let rest = &self.input[self.position + offset..];
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        let mut after = rest[digits..].chars();
+        digits > 0
+            && after.next().is_some_and(is_fraction_slash)
+            && after.next().is_some_and(|c| c.is_ascii_digit())
+    }
+
+    /// Consume digits, and group separators between them.
+    /// A space before a fraction is not a group separator, but part of a mixed number.
+    fn digits(&mut self) {
+        loop {
+            match self.peek(0) {
+                Some(c) if c.is_ascii_digit() => self.bump(),
+                Some(c)
+                    if self.config.group_separators.contains(&c)
+                        && c != self.config.decimal_separator
+                        && self.peek_digit(1)
+                        && !(c == ' ' && self.fraction_follows(1)) =>
+                {
+                    self.bump()
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn number(&mut self) -> TokenKind {
+        if self.peek(0) == Some('-') {
+            self.bump();
+        }
+        let digits = self.position;
+        self.digits();
+        let int_end = self.position;
+        let mut fraction = None;
+        let mut numerator = None;
+        let mut denominator = None;
+        if int_end > digits && self.peek(0) == Some(' ') && self.fraction_follows(1) {
+            // A mixed number like "1 1/2".
+            self.bump();
+            let start = self.position;
+            self.digits();
+            numerator = Some((start, self.position));
+        }
+        let vulgar = self.peek_vulgar(0);
+        if vulgar.is_some() {
+            self.bump();
+        } else if self.peek(0).is_some_and(is_fraction_slash) && self.peek_digit(1) {
+            self.bump();
+            let start = self.position;
+            self.digits();
+            denominator = Some((start, self.position));
+        } else if self.peek(0) == Some(self.config.decimal_separator) {
+            self.bump();
+            let start = self.position;
+            self.digits();
+            if self.position > start {
+                fraction = Some((start, self.position));
+            }
+        }
+        let exponent = self.peek(0) == Some('e')
+            && match self.peek(1) {
+                Some('-') | Some('+') => self.peek_digit(2),
+                Some(c) => c.is_ascii_digit(),
+                None => false,
+            };
+        if exponent {
+            self.bump();
+            if !self.peek_digit(0) {
+                self.bump();
+            }
+            while self.peek_digit(0) {
+                self.bump();
+            }
+        }
+        TokenKind::Number {
+            int_end,
+            fraction,
+            numerator,
+            denominator,
+            vulgar,
+            exponent,
+        }
+    }
+}
+
+impl Iterator for Lexer<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        let start = self.position;
+        let first = self.peek(0)?;
+        let kind = if self.starts_number() {
+            self.number()
+        } else if self.config.is_word_char(first) {
+            while self.peek(0).is_some_and(|c| self.config.is_word_char(c)) {
+                self.bump();
+            }
+            TokenKind::Word
+        } else {
+            while let Some(c) = self.peek(0) {
+                if c.is_ascii_digit() || self.config.is_word_char(c) || self.starts_number() {
+                    break;
+                }
+                self.bump();
+            }
+            TokenKind::Junk
+        };
+        Some(Token {
+            kind,
+            start,
+            end: self.position,
+        })
+    }
+}
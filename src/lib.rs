@@ -2,7 +2,7 @@
 //! The parser is based on the standard set by
 //! [systemd.time](https://www.freedesktop.org/software/systemd/man/systemd.time.html#Parsing%20Time%20Spans),
 //! but extends it significantly.
-//! For example, negative numbers, decimals and exponents are allowed.
+//! For example, negative numbers, decimals and fractions are allowed.
 //!
 //! ```
 //! use ::parse_duration0::parse;
@@ -10,7 +10,7 @@
 //!
 //! // One hour less than a day
 //! assert_eq!(parse("1 day -1 hour"), Ok(Duration::new(82_800, 0)));
-//! // Using exponents
+//! // Using decimals
 //! assert_eq!(parse("0.126 days"), Ok(Duration::new(10_886, 400_000_000)));
 //! // Extra things will be ignored
 //! assert_eq!(
@@ -26,8 +26,7 @@
 //! Spaces are not needed as in `"15days20seconds100milliseconds"`.
 //! Order doesn't matter at all.
 //!
-//! Characters other than alphanumeric (actually all word characters as defined by the regex crate)
-//! are ignored,
+//! Characters other than ASCII letters, digits and underscores are ignored,
 //! other than the fact that they act as a word boundary.
 //! So `".:++++]][][[][15[]][seconds][]:}}}}"` is the same as `"15 seconds"`.
 //!
@@ -80,6 +79,8 @@
 //!
 //! Years are defined using the average over 400 years in the Gregorian calendar.
 //! As such, a year is equivalent to 365.2425 days. A month is defined as one twelfth of a year.
+//! [`Options::calendar`](options/struct.Options.html#method.calendar) can change this to Julian
+//! years, to years of 365 days and months of 30 days, or reject years and months altogether.
 //!
 //! Abbreviations for each of these units are accepted.
 //! The general rule is that any initial segment of the full name is accepted as long as it's not
@@ -92,6 +93,22 @@
 //! (`"nsecs"`, `"usecs"`, `"μsecs"`, `"msecs"`, `"secs"`, `"mins"`, `"hrs"`, `"wks"`, `"yrs"`)
 //! are also accepted.
 //!
+//! Some less common units are only accepted by their full name, singular or plural,
+//! or by a fixed abbreviation, so that short words like `"de"` or `"c"` stay unknown:
+//!
+//! - fortnights, of two weeks
+//! - quarters (`"qtr"`), of three months
+//! - decades and centuries
+//! - jiffies, of 10 milliseconds
+//! - kiloseconds (`"ks"`), megaseconds and gigaseconds (`"Gs"`)
+//!
+//! Like `"M"`, the SI symbol `"Ms"` for megaseconds is case-sensitive: `"ms"` and `"MS"` are
+//! still milliseconds.
+//! [`Options::case_policy`](options/struct.Options.html#method.case_policy) can make units
+//! entirely case-insensitive or case-sensitive, or reject these ambiguous abbreviations.
+//! [`Options::spellings`](options/struct.Options.html#method.spellings) restricts units to a
+//! whitelist of [`Spellings`](spellings/struct.Spellings.html), like only the usual symbols.
+//!
 //!
 //! ```
 //! use ::parse_duration0::parse;
@@ -105,7 +122,8 @@
 //!
 //! # Values
 //!
-//! The values may be an integer, a decimal, or a mantissa with an exponent.
+//! The values may be an integer, a decimal, or a fraction like `1/2`, `½`, `1½` or `1 1/2`.
+//! Exponents like `1e5` are not supported.
 //! They may be as large as desired as long as the final duration is less than
 //! 2<sup>64</sup> seconds.
 //!
@@ -114,14 +132,36 @@
 //! When using negative values, the sum must end up non-negative, since `Duration`s are positive
 //! durations.
 //!
-//! Decimals are accurate up to nanosecond precision.
+//! With [`Options::natural_language`](options/struct.Options.html#method.natural_language),
+//! values may also be written as English words, like `"twenty minutes"` or `"half an hour"`.
+//!
+//! Anything that is not a value or a unit is ignored, so `"2h - 15m"` is 2 hours and 15 minutes.
+//! To subtract, multiply or divide durations, use
+//! [`parse_expression`](expression/fn.parse_expression.html) instead:
+//!
+//! ```
+//! use ::parse_duration0::parse_expression;
+//! use ::std::time::Duration;
+//!
+//! assert_eq!(parse_expression("2h - 15m"), Ok(Duration::new(6_300, 0)));
+//! assert_eq!(parse_expression("2 * 5min"), Ok(Duration::new(600, 0)));
+//! ```
+//!
+//! Decimals and fractions are accurate up to nanosecond precision.
 //! They will be rounded down to the nearest nanosecond if necessary.
 //!
+//! The decimal separator is `'.'`, and digits cannot be grouped.
+//! Both can be changed with [`Options`](options/struct.Options.html), so that for example
+//! `"1,5 h"` or `"1_000_000 ns"` are accepted.
+//!
 //! ```
 //! use ::parse_duration0::parse;
 //! use ::std::time::Duration;
 //!
 //! assert_eq!(parse("1 day -1 hour"), Ok(Duration::new(82_800, 0)));
+//! assert_eq!(parse("3/4 day"), Ok(Duration::new(64_800, 0)));
+//! assert_eq!(parse("1½ hours"), Ok(Duration::new(5_400, 0)));
+//! assert_eq!(parse("1 1/2 hours"), Ok(Duration::new(5_400, 0)));
 //!
 //! assert_eq!(parse("1844670000000000000 seconds"), Ok(Duration::new(1_844_670_000_000_000_000, 0)));
 //! assert_eq!(
@@ -130,22 +170,111 @@
 //! );
 //! ```
 //!
+//! # Languages
+//!
+//! Durations in other languages can be parsed with
+//! [`parse_localized`](locale/fn.parse_localized.html), and formatted with
+//! [`format_localized`](format/fn.format_localized.html).
+//! English is always available, other languages are enabled with cargo features:
+//! `locale-de` (German), `locale-es` (Spanish), `locale-fr` (French), `locale-ja` (Japanese)
+//! and `locale-nl` (Dutch), or `all-locales` for all of them.
+//!
+//! ```
+//! use ::parse_duration0::{format, locale, parse_localized};
+//! use ::std::time::Duration;
+//!
+//! assert_eq!(parse_localized(&locale::EN, "2 hours and 30 minutes"), Ok(Duration::new(9_000, 0)));
+//! assert_eq!(format(Duration::new(9_000, 0)), "2 hours and 30 minutes");
+//! ```
+//!
 //! # Errors
 //!
 //! The error `enum` has different variants for particular sorts of errors.
 //! See [the documentation for the error `enum`](parse/enum.Error.html) for more information.
 
-extern crate regex;
-#[macro_use]
-extern crate lazy_static;
-
 /// This module contains the parse function and the error `enum`.
 ///
 /// See the [module level documentation](index.html) for more.
 pub mod parse;
 
+/// This module contains the parser for arithmetic expressions of durations.
+pub mod expression;
+
+/// This module contains the parser for ranges of durations.
+pub mod range;
+
+/// This module contains the parser and formatter for Prometheus durations.
+pub mod prometheus;
+
+/// This module contains the parser for Go durations, as used by Kubernetes and Docker.
+pub mod go;
+
+/// This module contains the parser and formatter for Java durations.
+pub mod java;
+
+/// This module contains the parser and formatter for .NET `TimeSpan`s.
+pub mod dotnet;
+
+/// This module contains the parser and formatter for PostgreSQL intervals.
+pub mod postgres;
+
+/// This module contains the extended parser, for sub-nanosecond and very long durations.
+pub mod extended;
+
+/// This module contains the search for durations in free text.
+pub mod find;
+
+/// This module contains the formatters, which turn durations back into text.
+pub mod format;
+
+/// This module contains the C interface, see `ffi/parse_duration.h`.
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+mod lexer;
+
+/// This module contains the Python bindings.
+#[cfg(feature = "python")]
+pub mod python;
+
+/// This module contains the WebAssembly bindings.
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+/// This module contains the languages that durations can be written in.
+pub mod locale;
+
+/// This module contains the options that change which inputs are accepted.
+pub mod options;
+
+/// This module contains the reusable parser, for parsing many inputs with the same options.
+pub mod parser;
+
+/// This module contains the whitelists of unit spellings.
+pub mod spellings;
+
+mod words;
+
+pub use self::dotnet::{format_dotnet, parse_dotnet};
+pub use self::expression::{parse_expression, parse_expression_with};
+pub use self::extended::{parse_extended, ExtendedDuration, ExtendedUnit};
+pub use self::find::{find_durations, FindDurations, FoundDuration};
+pub use self::format::{format, format_iso8601, format_localized};
+pub use self::go::{lint_go, parse_go, parse_go_with, GoOptions};
+pub use self::java::{format_java, parse_java};
+pub use self::locale::{parse_localized, Locale};
+pub use self::options::{parse_bounded, parse_hardened, parse_with, Options};
 pub use self::parse::parse;
+pub use self::parse::Calendar;
+pub use self::parse::CasePolicy;
 pub use self::parse::Error;
+pub use self::parse::SpannedError;
+pub use self::parse::Unit;
+pub use self::parser::{ParseLines, ParseMany, Parser};
+pub use self::postgres::{format_postgres, parse_postgres, IntervalStyle, PgInterval};
+pub use self::prometheus::{format_prometheus, parse_prometheus};
+pub use self::range::{parse_range, parse_range_with, DurationRange};
+pub use self::spellings::Spellings;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,589 @@
+use ::std::time::Duration;
+
+use crate::options::{parse_with, Options};
+use crate::parse::{Error, Unit};
+
+/// The words a language uses for a single unit.
+#[derive(Debug)]
+struct UnitWords {
+    unit: Unit,
+    /// Used when formatting a single unit, e.g. "Stunde".
+    singular: &'static str,
+    /// Used when formatting several units, e.g. "Stunden".
+    plural: &'static str,
+    /// Other accepted spellings, e.g. "Std" or "Stundes".
+    other: &'static [&'static str],
+}
+
+/// The unit words, plural rule, decimal separator and conjunctions of a language.
+///
+/// English is always available as [`EN`](static.EN.html).
+/// Other languages are enabled with the `locale-xx` cargo features, or all at once with `all-locales`.
+#[derive(Debug)]
+pub struct Locale {
+    /// The ISO 639-1 code of the language, e.g. `"de"`.
+    pub code: &'static str,
+    units: &'static [UnitWords],
+    /// The character between the integer and fractional part of a value, e.g. `','` in German.
+    decimal_separator: char,
+    /// Words like "and" that may join the parts of a duration.
+    conjunctions: &'static [&'static str],
+    /// Put between the value and the unit when formatting, e.g. `" "` in "5 minutes".
+    value_separator: &'static str,
+    /// Put between the components when formatting, e.g. `" "` in "1 day 1 hour 5 minutes".
+    component_separator: &'static str,
+    /// Put between the last two components when formatting, e.g. `" and "` in "1 hour and 5 minutes".
+    last_separator: &'static str,
+    /// Whether the plural form should be used for a value.
+    is_plural: fn(u64) -> bool,
+}
+
+impl Locale {
+    /// Find the unit for a word of this language.
+    /// This only knows the words of this language itself, not the English fallback used by
+    /// [`parse_localized`](fn.parse_localized.html).
+    pub fn unit(&self, word: &str) -> Option<Unit> {
+        self.units
+            .iter()
+            .find(|words| {
+                eq_casefold(word, words.singular)
+                    || eq_casefold(word, words.plural)
+                    || words.other.iter().any(|other| eq_casefold(word, other))
+            })
+            .map(|words| words.unit)
+    }
+
+    /// Whether `word` is a conjunction like "and" in this language.
+    pub fn is_conjunction(&self, word: &str) -> bool {
+        self.conjunctions
+            .iter()
+            .any(|conjunction| eq_casefold(word, conjunction))
+    }
+
+    /// `word` without a conjunction at its start or end, if it has one and more than that.
+    /// Languages like Japanese do not put spaces around conjunctions, so "時間と" is "時間".
+    pub(crate) fn strip_conjunction<'a>(&self, word: &'a str) -> Option<&'a str> {
+        self.conjunctions.iter().find_map(|conjunction| {
+            let len = conjunction.len();
+            if word.len() <= len {
+                return None;
+            }
+            let (head, tail) = word.split_at_checked(word.len() - len)?;
+            if self.is_conjunction(tail) {
+                return Some(head);
+            }
+            let (head, tail) = word.split_at_checked(len)?;
+            Some(tail).filter(|_| self.is_conjunction(head))
+        })
+    }
+
+    /// The name to use for `value` times `unit`, e.g. "Stunden" for 2 hours.
+    pub(crate) fn unit_name(&self, unit: Unit, value: u64) -> &'static str {
+        let words = self
+            .units
+            .iter()
+            .find(|words| words.unit == unit)
+            .expect("every locale names every unit");
+        if (self.is_plural)(value) {
+            words.plural
+        } else {
+            words.singular
+        }
+    }
+
+    /// The character between the integer and fractional part of a value, e.g. `','` in German.
+    pub fn decimal_separator(&self) -> char {
+        self.decimal_separator
+    }
+
+    pub(crate) fn value_separator(&self) -> &'static str {
+        self.value_separator
+    }
+
+    pub(crate) fn component_separator(&self) -> &'static str {
+        self.component_separator
+    }
+
+    pub(crate) fn last_separator(&self) -> &'static str {
+        self.last_separator
+    }
+}
+
+/// Compare two words ignoring case, without allocating.
+fn eq_casefold(left: &str, right: &str) -> bool {
+    left.chars()
+        .flat_map(char::to_lowercase)
+        .eq(right.chars().flat_map(char::to_lowercase))
+}
+
+/// Parse a string written in the language of `locale` into a duration object.
+///
+/// The syntax is that of [`parse`](../parse/fn.parse.html), but unit words may be in the given
+/// language as well as in English, so that abbreviations like "ms" or "h" keep working.
+/// Words of the language take precedence, e.g. "u" means "uur" (hours) in Dutch.
+/// Conjunctions such as "und" are ignored, like any word without a value, and are split off
+/// unit words they are written against, as in "1時間と30分" in Japanese.
+/// Values use the decimal separator of the language, e.g. "1,5 Stunden" in German.
+///
+/// ```
+/// use ::parse_duration0::{locale, parse_localized};
+/// use ::std::time::Duration;
+///
+/// assert_eq!(
+///     parse_localized(&locale::EN, "1 hour and 30 minutes"),
+///     Ok(Duration::new(5_400, 0))
+/// );
+/// ```
+pub fn parse_localized(locale: &'static Locale, input: &str) -> Result<Duration, Error> {
+    parse_with(input, &Options::new().locale(locale))
+}
+
+fn plural_unless_one(value: u64) -> bool {
+    value != 1
+}
+
+/// English.
+pub static EN: Locale = Locale {
+    code: "en",
+    decimal_separator: '.',
+    units: &[
+        UnitWords {
+            unit: Unit::Nanosecond,
+            singular: "nanosecond",
+            plural: "nanoseconds",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Microsecond,
+            singular: "microsecond",
+            plural: "microseconds",
+            other: &["µs", "μs"],
+        },
+        UnitWords {
+            unit: Unit::Millisecond,
+            singular: "millisecond",
+            plural: "milliseconds",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Second,
+            singular: "second",
+            plural: "seconds",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Minute,
+            singular: "minute",
+            plural: "minutes",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Hour,
+            singular: "hour",
+            plural: "hours",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Day,
+            singular: "day",
+            plural: "days",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Week,
+            singular: "week",
+            plural: "weeks",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Month,
+            singular: "month",
+            plural: "months",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Year,
+            singular: "year",
+            plural: "years",
+            other: &[],
+        },
+    ],
+    conjunctions: &["and"],
+    value_separator: " ",
+    component_separator: " ",
+    last_separator: " and ",
+    is_plural: plural_unless_one,
+};
+
+/// German.
+#[cfg(feature = "locale-de")]
+pub static DE: Locale = Locale {
+    code: "de",
+    decimal_separator: ',',
+    units: &[
+        UnitWords {
+            unit: Unit::Nanosecond,
+            singular: "Nanosekunde",
+            plural: "Nanosekunden",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Microsecond,
+            singular: "Mikrosekunde",
+            plural: "Mikrosekunden",
+            other: &["µs"],
+        },
+        UnitWords {
+            unit: Unit::Millisecond,
+            singular: "Millisekunde",
+            plural: "Millisekunden",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Second,
+            singular: "Sekunde",
+            plural: "Sekunden",
+            other: &["Sek"],
+        },
+        UnitWords {
+            unit: Unit::Minute,
+            singular: "Minute",
+            plural: "Minuten",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Hour,
+            singular: "Stunde",
+            plural: "Stunden",
+            other: &["Std"],
+        },
+        UnitWords {
+            unit: Unit::Day,
+            singular: "Tag",
+            plural: "Tage",
+            other: &["Tagen", "T"],
+        },
+        UnitWords {
+            unit: Unit::Week,
+            singular: "Woche",
+            plural: "Wochen",
+            other: &["Wo"],
+        },
+        UnitWords {
+            unit: Unit::Month,
+            singular: "Monat",
+            plural: "Monate",
+            other: &["Monaten", "Mon"],
+        },
+        UnitWords {
+            unit: Unit::Year,
+            singular: "Jahr",
+            plural: "Jahre",
+            other: &["Jahren", "J"],
+        },
+    ],
+    conjunctions: &["und"],
+    value_separator: " ",
+    component_separator: " ",
+    last_separator: " und ",
+    is_plural: plural_unless_one,
+};
+
+/// French.
+#[cfg(feature = "locale-fr")]
+pub static FR: Locale = Locale {
+    code: "fr",
+    decimal_separator: ',',
+    units: &[
+        UnitWords {
+            unit: Unit::Nanosecond,
+            singular: "nanoseconde",
+            plural: "nanosecondes",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Microsecond,
+            singular: "microseconde",
+            plural: "microsecondes",
+            other: &["µs"],
+        },
+        UnitWords {
+            unit: Unit::Millisecond,
+            singular: "milliseconde",
+            plural: "millisecondes",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Second,
+            singular: "seconde",
+            plural: "secondes",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Minute,
+            singular: "minute",
+            plural: "minutes",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Hour,
+            singular: "heure",
+            plural: "heures",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Day,
+            singular: "jour",
+            plural: "jours",
+            other: &["j"],
+        },
+        UnitWords {
+            unit: Unit::Week,
+            singular: "semaine",
+            plural: "semaines",
+            other: &["sem"],
+        },
+        UnitWords {
+            unit: Unit::Month,
+            singular: "mois",
+            plural: "mois",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Year,
+            singular: "an",
+            plural: "ans",
+            other: &["année", "années"],
+        },
+    ],
+    conjunctions: &["et"],
+    value_separator: " ",
+    component_separator: " ",
+    last_separator: " et ",
+    // French uses the singular for zero and one.
+    is_plural: |value| value > 1,
+};
+
+/// Spanish.
+#[cfg(feature = "locale-es")]
+pub static ES: Locale = Locale {
+    code: "es",
+    decimal_separator: ',',
+    units: &[
+        UnitWords {
+            unit: Unit::Nanosecond,
+            singular: "nanosegundo",
+            plural: "nanosegundos",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Microsecond,
+            singular: "microsegundo",
+            plural: "microsegundos",
+            other: &["µs"],
+        },
+        UnitWords {
+            unit: Unit::Millisecond,
+            singular: "milisegundo",
+            plural: "milisegundos",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Second,
+            singular: "segundo",
+            plural: "segundos",
+            other: &["seg"],
+        },
+        UnitWords {
+            unit: Unit::Minute,
+            singular: "minuto",
+            plural: "minutos",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Hour,
+            singular: "hora",
+            plural: "horas",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Day,
+            singular: "día",
+            plural: "días",
+            other: &["dia", "dias"],
+        },
+        UnitWords {
+            unit: Unit::Week,
+            singular: "semana",
+            plural: "semanas",
+            other: &["sem"],
+        },
+        UnitWords {
+            unit: Unit::Month,
+            singular: "mes",
+            plural: "meses",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Year,
+            singular: "año",
+            plural: "años",
+            other: &[],
+        },
+    ],
+    conjunctions: &["y"],
+    value_separator: " ",
+    component_separator: " ",
+    last_separator: " y ",
+    is_plural: plural_unless_one,
+};
+
+/// Dutch.
+#[cfg(feature = "locale-nl")]
+pub static NL: Locale = Locale {
+    code: "nl",
+    decimal_separator: ',',
+    units: &[
+        UnitWords {
+            unit: Unit::Nanosecond,
+            singular: "nanoseconde",
+            plural: "nanoseconden",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Microsecond,
+            singular: "microseconde",
+            plural: "microseconden",
+            other: &["µs"],
+        },
+        UnitWords {
+            unit: Unit::Millisecond,
+            singular: "milliseconde",
+            plural: "milliseconden",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Second,
+            singular: "seconde",
+            plural: "seconden",
+            other: &["sec"],
+        },
+        UnitWords {
+            unit: Unit::Minute,
+            singular: "minuut",
+            plural: "minuten",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Hour,
+            singular: "uur",
+            // "2 uur" is much more common than "2 uren".
+            plural: "uur",
+            other: &["uren", "u"],
+        },
+        UnitWords {
+            unit: Unit::Day,
+            singular: "dag",
+            plural: "dagen",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Week,
+            singular: "week",
+            plural: "weken",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Month,
+            singular: "maand",
+            plural: "maanden",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Year,
+            singular: "jaar",
+            // Like "uur", "2 jaar" is more common than "2 jaren".
+            plural: "jaar",
+            other: &["jaren", "j"],
+        },
+    ],
+    conjunctions: &["en"],
+    value_separator: " ",
+    component_separator: " ",
+    last_separator: " en ",
+    is_plural: plural_unless_one,
+};
+
+/// Japanese.
+#[cfg(feature = "locale-ja")]
+pub static JA: Locale = Locale {
+    code: "ja",
+    decimal_separator: '.',
+    units: &[
+        UnitWords {
+            unit: Unit::Nanosecond,
+            singular: "ナノ秒",
+            plural: "ナノ秒",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Microsecond,
+            singular: "マイクロ秒",
+            plural: "マイクロ秒",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Millisecond,
+            singular: "ミリ秒",
+            plural: "ミリ秒",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Second,
+            singular: "秒",
+            plural: "秒",
+            other: &["秒間"],
+        },
+        UnitWords {
+            unit: Unit::Minute,
+            singular: "分",
+            plural: "分",
+            other: &["分間"],
+        },
+        UnitWords {
+            unit: Unit::Hour,
+            singular: "時間",
+            plural: "時間",
+            other: &[],
+        },
+        UnitWords {
+            unit: Unit::Day,
+            singular: "日",
+            plural: "日",
+            other: &["日間"],
+        },
+        UnitWords {
+            unit: Unit::Week,
+            singular: "週間",
+            plural: "週間",
+            other: &["週"],
+        },
+        UnitWords {
+            unit: Unit::Month,
+            singular: "か月",
+            plural: "か月",
+            other: &["ヶ月", "カ月", "ヵ月", "ケ月", "箇月", "ヶ月間", "か月間"],
+        },
+        UnitWords {
+            unit: Unit::Year,
+            singular: "年",
+            plural: "年",
+            other: &["年間"],
+        },
+    ],
+    conjunctions: &["と"],
+    value_separator: "",
+    component_separator: "",
+    last_separator: "",
+    is_plural: |_| false,
+};
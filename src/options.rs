@@ -0,0 +1,414 @@
+use ::std::time::Duration;
+
+use crate::lexer::LexerConfig;
+use crate::locale::Locale;
+use crate::parse::{
+    case_candidates, parse_with_units, Calendar, CasePolicy, Error, ParseConfig, Unit,
+};
+use crate::spellings::Spellings;
+
+/// Options that change which inputs [`parse_with`](fn.parse_with.html) accepts.
+///
+/// The default options accept exactly what [`parse`](../parse/fn.parse.html) accepts.
+///
+/// ```
+/// use ::parse_duration0::{locale, parse_with, Options};
+/// use ::std::time::Duration;
+///
+/// let options = Options::new().group_separators(&['_', ' ']);
+/// assert_eq!(parse_with("1_000_000 ns", &options), Ok(Duration::new(0, 1_000_000)));
+/// assert_eq!(parse_with("1 000 ms", &options), Ok(Duration::new(1, 0)));
+///
+/// let options = Options::new().decimal_separator(',');
+/// assert_eq!(parse_with("1,5 h", &options), Ok(Duration::new(5_400, 0)));
+///
+/// let options = Options::new().max(Duration::new(86_400, 0));
+/// assert!(parse_with("2 days", &options).is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    locale: Option<&'static Locale>,
+    decimal_separator: Option<char>,
+    group_separators: Vec<char>,
+    natural_language: bool,
+    strict: bool,
+    min: Option<Duration>,
+    max: Option<Duration>,
+    allowed_units: Option<Vec<Unit>>,
+    disallow_zero: bool,
+    max_length: Option<usize>,
+    max_components: Option<usize>,
+    calendar: Calendar,
+    case_policy: CasePolicy,
+    spellings: Option<Spellings>,
+}
+
+/// The maximum length of an input with [`Options::hardened`](struct.Options.html#method.hardened),
+/// in bytes.
+pub const HARDENED_MAX_LENGTH: usize = 256;
+
+/// The maximum number of values in an input with
+/// [`Options::hardened`](struct.Options.html#method.hardened).
+pub const HARDENED_MAX_COMPONENTS: usize = 16;
+
+impl Options {
+    /// The default options.
+    pub fn new() -> Self {
+        Options::default()
+    }
+
+    /// Accept unit words of this language, as [`parse_localized`](../locale/fn.parse_localized.html) does.
+    /// This also sets the decimal separator to that of the language,
+    /// unless [`decimal_separator`](#method.decimal_separator) is used.
+    pub fn locale(mut self, locale: &'static Locale) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+
+    /// The character between the integer and the fractional part of a value, `'.'` by default.
+    pub fn decimal_separator(mut self, separator: char) -> Self {
+        self.decimal_separator = Some(separator);
+        self
+    }
+
+    /// Characters that may be used to group digits, like `'_'` in `"1_000_000"` or `' '` in
+    /// `"1 000 000"`. None by default.
+    ///
+    /// A group separator is only recognized directly between two digits.
+    /// Note that with `' '` as a separator, `"16 17 seconds"` is read as 1617 seconds.
+    /// The decimal separator can not also be a group separator.
+    pub fn group_separators(mut self, separators: &[char]) -> Self {
+        self.group_separators = separators.to_vec();
+        self
+    }
+
+    /// Also accept values written as English words, like "twenty minutes", "a week",
+    /// "half an hour", "three quarters of an hour" or "two and a half days".
+    ///
+    /// Words like "and" and "a" are only read as part of a value where that makes sense,
+    /// so "take a break for an hour" is one hour.
+    ///
+    /// ```
+    /// use ::parse_duration0::{parse_with, Options};
+    /// use ::std::time::Duration;
+    ///
+    /// let options = Options::new().natural_language(true);
+    /// assert_eq!(parse_with("remind me in twenty minutes", &options), Ok(Duration::new(1_200, 0)));
+    /// assert_eq!(parse_with("an hour and a half", &options), Ok(Duration::new(5_400, 0)));
+    /// ```
+    pub fn natural_language(mut self, enabled: bool) -> Self {
+        self.natural_language = enabled;
+        self
+    }
+
+    /// Only accept values with units, separated by whitespace, like `"1h 30m"`.
+    ///
+    /// Other characters and words are normally ignored, but in strict mode they are an
+    /// [`Error::UnexpectedToken`](../parse/enum.Error.html#variant.UnexpectedToken).
+    /// A lone number like `"15"` is an [`Error::NoUnitFound`](../parse/enum.Error.html#variant.NoUnitFound)
+    /// instead of seconds.
+    ///
+    /// ```
+    /// use ::parse_duration0::{parse_with, Error, Options};
+    /// use ::std::time::Duration;
+    ///
+    /// let options = Options::new().strict(true);
+    /// assert_eq!(parse_with("1h 30m", &options), Ok(Duration::new(5_400, 0)));
+    /// assert_eq!(parse_with("1h, 30m", &options), Err(Error::UnexpectedToken(",".to_string())));
+    /// ```
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Reject durations shorter than `min` with [`Error::TooShort`](../parse/enum.Error.html#variant.TooShort).
+    pub fn min(mut self, min: Duration) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    /// Reject durations longer than `max` with [`Error::TooLong`](../parse/enum.Error.html#variant.TooLong).
+    pub fn max(mut self, max: Duration) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Only accept these units. Other units are rejected with
+    /// [`Error::UnitNotAllowed`](../parse/enum.Error.html#variant.UnitNotAllowed).
+    ///
+    /// A lone number without a unit, like `"15"`, is read as seconds, so it is rejected
+    /// if seconds are not allowed.
+    ///
+    /// ```
+    /// use ::parse_duration0::{parse_with, Error, Options, Unit};
+    ///
+    /// let options = Options::new().allowed_units(&[Unit::Second, Unit::Minute]);
+    /// assert_eq!(
+    ///     parse_with("1 month", &options),
+    ///     Err(Error::UnitNotAllowed { word: "month".to_string(), unit: Unit::Month })
+    /// );
+    /// ```
+    pub fn allowed_units(mut self, units: &[Unit]) -> Self {
+        self.allowed_units = Some(units.to_vec());
+        self
+    }
+
+    /// Reject a zero duration with [`Error::ZeroNotAllowed`](../parse/enum.Error.html#variant.ZeroNotAllowed).
+    pub fn disallow_zero(mut self, disallow: bool) -> Self {
+        self.disallow_zero = disallow;
+        self
+    }
+
+    /// Reject inputs longer than `bytes` with
+    /// [`Error::InputTooLong`](../parse/enum.Error.html#variant.InputTooLong), before reading them.
+    pub fn max_length(mut self, bytes: usize) -> Self {
+        self.max_length = Some(bytes);
+        self
+    }
+
+    /// Reject inputs with more than `count` values, like the three in `"1h 2m 3s"`, with
+    /// [`Error::TooManyComponents`](../parse/enum.Error.html#variant.TooManyComponents).
+    /// The rest of the input is not read.
+    pub fn max_components(mut self, count: usize) -> Self {
+        self.max_components = Some(count);
+        self
+    }
+
+    /// How long years and months are, [`Calendar::Gregorian`](../parse/enum.Calendar.html#variant.Gregorian)
+    /// by default. This applies to whole and decimal values alike.
+    ///
+    /// ```
+    /// use ::parse_duration0::{parse_with, Calendar, Error, Options, Unit};
+    /// use ::std::time::Duration;
+    ///
+    /// let options = Options::new().calendar(Calendar::Fixed);
+    /// assert_eq!(parse_with("1 year", &options), Ok(Duration::new(365 * 86_400, 0)));
+    /// assert_eq!(parse_with("1.5 months", &options), Ok(Duration::new(45 * 86_400, 0)));
+    ///
+    /// let options = Options::new().calendar(Calendar::Julian);
+    /// assert_eq!(parse_with("1 year", &options), Ok(Duration::new(31_557_600, 0)));
+    ///
+    /// let options = Options::new().calendar(Calendar::Reject);
+    /// assert_eq!(
+    ///     parse_with("1 month", &options),
+    ///     Err(Error::UnitNotAllowed { word: "month".to_string(), unit: Unit::Month })
+    /// );
+    /// ```
+    pub fn calendar(mut self, calendar: Calendar) -> Self {
+        self.calendar = calendar;
+        self
+    }
+
+    /// How the case of a unit matters, [`CasePolicy::Mixed`](../parse/enum.CasePolicy.html#variant.Mixed)
+    /// by default. Words of a [`locale`](#method.locale) are not affected.
+    ///
+    /// ```
+    /// use ::parse_duration0::{parse_with, CasePolicy, Error, Options, Unit};
+    /// use ::std::time::Duration;
+    ///
+    /// let options = Options::new().case_policy(CasePolicy::Insensitive);
+    /// assert_eq!(parse_with("5M", &options), Ok(Duration::new(300, 0)));
+    ///
+    /// let options = Options::new().case_policy(CasePolicy::RejectAmbiguous);
+    /// assert_eq!(parse_with("5 min", &options), Ok(Duration::new(300, 0)));
+    /// assert_eq!(
+    ///     parse_with("5M", &options),
+    ///     Err(Error::AmbiguousUnit {
+    ///         word: "M".to_string(),
+    ///         candidates: vec![Unit::Minute, Unit::Month],
+    ///     })
+    /// );
+    /// ```
+    pub fn case_policy(mut self, policy: CasePolicy) -> Self {
+        self.case_policy = policy;
+        self
+    }
+
+    /// Only accept these spellings of units, instead of any initial segment of their names.
+    /// Words of a [`locale`](#method.locale) are not affected.
+    ///
+    /// ```
+    /// use ::parse_duration0::{parse_with, Error, Options, Spellings};
+    /// use ::std::time::Duration;
+    ///
+    /// let options = Options::new().spellings(Spellings::canonical_long());
+    /// assert_eq!(parse_with("1 hour", &options), Ok(Duration::new(3_600, 0)));
+    /// assert_eq!(parse_with("1 mill", &options), Err(Error::UnknownUnit("mill".to_string())));
+    /// ```
+    pub fn spellings(mut self, spellings: Spellings) -> Self {
+        self.spellings = Some(spellings);
+        self
+    }
+
+    /// Options for inputs from untrusted sources, like a public API: at most
+    /// [`HARDENED_MAX_LENGTH`](constant.HARDENED_MAX_LENGTH.html) bytes and
+    /// [`HARDENED_MAX_COMPONENTS`](constant.HARDENED_MAX_COMPONENTS.html) values.
+    /// Other options can be added as usual.
+    ///
+    /// ```
+    /// use ::parse_duration0::{parse_with, Error, Options};
+    /// use ::std::time::Duration;
+    ///
+    /// let options = Options::hardened();
+    /// assert_eq!(parse_with("1h 30m", &options), Ok(Duration::new(5_400, 0)));
+    /// assert_eq!(parse_with(&"1s".repeat(17), &options), Err(Error::TooManyComponents { max: 16 }));
+    /// ```
+    pub fn hardened() -> Self {
+        Options::new()
+            .max_length(HARDENED_MAX_LENGTH)
+            .max_components(HARDENED_MAX_COMPONENTS)
+    }
+
+    /// Find the unit for a word, in the language of these options or in English,
+    /// if it is allowed.
+    pub(crate) fn resolve(&self, word: &str) -> Option<Unit> {
+        self.resolve_any(word).filter(|unit| self.allows(*unit))
+    }
+
+    fn resolve_any(&self, word: &str) -> Option<Unit> {
+        self.resolve_word(word).or_else(|| {
+            let stripped = self.locale?.strip_conjunction(word)?;
+            self.resolve_word(stripped)
+        })
+    }
+
+    fn resolve_word(&self, word: &str) -> Option<Unit> {
+        self.locale
+            .and_then(|locale| locale.unit(word))
+            .or_else(|| match &self.spellings {
+                Some(spellings) => spellings.resolve(word, self.case_policy),
+                None => self.case_policy.resolve(word),
+            })
+    }
+
+    /// The units that `word` could be, if the case policy rejects it as ambiguous.
+    fn ambiguity(&self, word: &str) -> Option<Vec<Unit>> {
+        if self.case_policy != CasePolicy::RejectAmbiguous {
+            return None;
+        }
+        match &self.spellings {
+            Some(spellings) => Some(spellings.candidates(word)).filter(|units| units.len() > 1),
+            None => case_candidates(word).map(|units| units.to_vec()),
+        }
+    }
+
+    fn allows(&self, unit: Unit) -> bool {
+        self.calendar.allows(unit)
+            && self
+                .allowed_units
+                .as_ref()
+                .is_none_or(|units| units.contains(&unit))
+    }
+
+    /// Turn an unknown unit into `UnitNotAllowed` if it is only unknown because it is not allowed,
+    /// or into `AmbiguousUnit` if the case policy rejects it.
+    pub(crate) fn check_unit(&self, error: Error) -> Error {
+        match error {
+            Error::UnknownUnit(word) => match self.resolve_any(&word) {
+                Some(unit) => Error::UnitNotAllowed { word, unit },
+                None => match self.ambiguity(&word) {
+                    Some(candidates) => Error::AmbiguousUnit { word, candidates },
+                    None => Error::UnknownUnit(word),
+                },
+            },
+            error => error,
+        }
+    }
+
+    /// Check that the input is not longer than the maximum of these options.
+    pub(crate) fn check_length(&self, input: &str) -> Result<(), Error> {
+        match self.max_length {
+            Some(max) if input.len() > max => Err(Error::InputTooLong {
+                max,
+                length: input.len(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that the duration is within the bounds of these options.
+    pub(crate) fn check_bounds(&self, value: Duration) -> Result<Duration, Error> {
+        if self.disallow_zero && value == Duration::new(0, 0) {
+            return Err(Error::ZeroNotAllowed);
+        }
+        match (self.min, self.max) {
+            (Some(min), _) if value < min => Err(Error::TooShort { min, value }),
+            (_, Some(max)) if value > max => Err(Error::TooLong { max, value }),
+            _ => Ok(value),
+        }
+    }
+
+    pub(crate) fn lexer_config(&self) -> LexerConfig<'_> {
+        LexerConfig {
+            decimal_separator: self
+                .decimal_separator
+                .or_else(|| self.locale.map(Locale::decimal_separator))
+                .unwrap_or('.'),
+            group_separators: &self.group_separators,
+            unicode_words: self.locale.is_some(),
+        }
+    }
+
+    pub(crate) fn parse_config(&self) -> ParseConfig<'_> {
+        ParseConfig {
+            lexer: self.lexer_config(),
+            natural_language: self.natural_language,
+            strict: self.strict,
+            max_components: self.max_components,
+            calendar: self.calendar,
+            seconds_allowed: self.allows(Unit::Second),
+        }
+    }
+}
+
+/// Parse a string into a duration object, with the given options.
+///
+/// See the [module level documentation](../index.html) for the syntax,
+/// and [`Options`](struct.Options.html) for what can be changed.
+pub fn parse_with(input: &str, options: &Options) -> Result<Duration, Error> {
+    options.check_length(input)?;
+    parse_with_units(input, options.parse_config(), |word| options.resolve(word))
+        .map_err(|error| options.check_unit(error))
+        .and_then(|value| options.check_bounds(value))
+}
+
+/// Parse a string from an untrusted source into a duration object.
+///
+/// This is [`parse_with`](fn.parse_with.html) with [`Options::hardened`](struct.Options.html#method.hardened),
+/// which limits how long the input may be and how many values it may have, so that a caller can
+/// not make it do much work. [`parse`](../parse/fn.parse.html) reads inputs of any length.
+///
+/// ```
+/// use ::parse_duration0::{parse_hardened, Error};
+/// use ::std::time::Duration;
+///
+/// assert_eq!(parse_hardened("1 day -1 hour"), Ok(Duration::new(82_800, 0)));
+/// assert_eq!(
+///     parse_hardened(&" ".repeat(300)),
+///     Err(Error::InputTooLong { max: 256, length: 300 })
+/// );
+/// ```
+pub fn parse_hardened(input: &str) -> Result<Duration, Error> {
+    parse_with(input, &Options::hardened())
+}
+
+/// Parse a string into a duration object, and check that it is between `min` and `max`,
+/// inclusive.
+///
+/// This is [`parse_with`](fn.parse_with.html) with [`Options::min`](struct.Options.html#method.min)
+/// and [`Options::max`](struct.Options.html#method.max). Use those to combine bounds with other options.
+///
+/// ```
+/// use ::parse_duration0::{parse_bounded, Error};
+/// use ::std::time::Duration;
+///
+/// let (min, max) = (Duration::new(1, 0), Duration::new(86_400, 0));
+/// assert_eq!(parse_bounded("15 minutes", min, max), Ok(Duration::new(900, 0)));
+/// assert_eq!(
+///     parse_bounded("500ms", min, max),
+///     Err(Error::TooShort { min, value: Duration::new(0, 500_000_000) })
+/// );
+/// ```
+pub fn parse_bounded(input: &str, min: Duration, max: Duration) -> Result<Duration, Error> {
+    parse_with(input, &Options::new().min(min).max(max))
+}
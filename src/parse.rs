@@ -1,12 +1,20 @@
+use ::std::cell::Cell;
 use ::std::convert::TryInto;
 use ::std::error::Error as ErrorTrait;
 use ::std::fmt;
 use ::std::time::Duration;
 
-use ::regex::Regex;
+use ::std::iter::Peekable;
+use ::std::ops::Range;
+
+use crate::lexer::{Lexer, LexerConfig, Token, TokenKind};
+use crate::words;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 /// An enumeration of the possible errors while parsing.
+///
+/// New variants may be added in minor versions, so a `match` on it needs a wildcard arm.
+#[non_exhaustive]
 pub enum Error {
     // Failed to parse the number, including too large numbers.
     ParseInt(String),
@@ -23,6 +31,52 @@ pub enum Error {
     NoUnitFound(String),
     /// No value at all was found.
     NoValueFound(String),
+    /// A fraction like `1/0` has a zero denominator, or an expression divides by zero.
+    DivisionByZero(String),
+    /// An expression contains something that does not belong at that place.
+    UnexpectedToken(String),
+    /// An expression ended too early, e.g. `"1h +"` or `"(1h"`.
+    UnexpectedEnd,
+    /// An expression combines values that do not fit together, e.g. `"1h * 2h"`.
+    InvalidOperation(String),
+    /// An expression has too many nested parentheses.
+    NestingTooDeep,
+    /// A range has no recognizable form, or its start is after its end.
+    InvalidRange(String),
+    /// The duration is shorter than the minimum of the options.
+    TooShort {
+        min: Duration,
+        value: Duration,
+    },
+    /// The duration is longer than the maximum of the options.
+    TooLong {
+        max: Duration,
+        value: Duration,
+    },
+    /// A unit was used that the options do not allow.
+    UnitNotAllowed {
+        word: String,
+        unit: Unit,
+    },
+    /// The duration is zero, which the options do not allow.
+    ZeroNotAllowed,
+    /// The input does not follow a fixed format, like that of Prometheus.
+    InvalidFormat(String),
+    /// The input is longer than the maximum of the options, in bytes.
+    InputTooLong {
+        max: usize,
+        length: usize,
+    },
+    /// The input has more values than the maximum of the options.
+    TooManyComponents {
+        max: usize,
+    },
+    /// A unit abbreviation means different units depending on its case, like `"m"` and `"M"`,
+    /// which the options do not allow.
+    AmbiguousUnit {
+        word: String,
+        candidates: Vec<Unit>,
+    },
 }
 
 impl fmt::Display for Error {
@@ -51,6 +105,59 @@ impl fmt::Display for Error {
             Error::ExpNotSupported => {
                 write!(f, "Exponential notation not supported (i.e. not 2.3e4)")
             }
+            Error::DivisionByZero(ref s) => {
+                write!(f, "DivisionByZeroError: \"{}\" divides by zero", s)
+            }
+            Error::UnexpectedToken(ref s) => {
+                write!(f, "UnexpectedTokenError: did not expect \"{}\"", s)
+            }
+            Error::UnexpectedEnd => write!(f, "UnexpectedEndError: the input ended too early"),
+            Error::InvalidOperation(ref s) => write!(f, "InvalidOperationError: {}", s),
+            Error::NestingTooDeep => {
+                write!(f, "NestingTooDeepError: too many nested parentheses")
+            }
+            Error::InvalidRange(ref s) => {
+                write!(f, "InvalidRangeError: \"{}\" is not a valid range", s)
+            }
+            Error::TooShort { min, value } => write!(
+                f,
+                "TooShortError: {:?} is shorter than the minimum of {:?}",
+                value, min
+            ),
+            Error::TooLong { max, value } => write!(
+                f,
+                "TooLongError: {:?} is longer than the maximum of {:?}",
+                value, max
+            ),
+            Error::UnitNotAllowed { ref word, unit } => write!(
+                f,
+                "UnitNotAllowedError: \"{}\" ({:?}) is not an allowed unit",
+                word, unit
+            ),
+            Error::ZeroNotAllowed => {
+                write!(f, "ZeroNotAllowedError: the duration must not be zero")
+            }
+            Error::InvalidFormat(ref s) => {
+                write!(f, "InvalidFormatError: \"{}\" is not a valid duration", s)
+            }
+            Error::InputTooLong { max, length } => write!(
+                f,
+                "InputTooLongError: the input is {} bytes long, more than the maximum of {}",
+                length, max
+            ),
+            Error::TooManyComponents { max } => write!(
+                f,
+                "TooManyComponentsError: the input has more than {} values",
+                max
+            ),
+            Error::AmbiguousUnit {
+                ref word,
+                ref candidates,
+            } => write!(
+                f,
+                "AmbiguousUnitError: \"{}\" could be any of {:?}",
+                word, candidates
+            ),
         }
     }
 }
@@ -65,16 +172,205 @@ impl ErrorTrait for Error {
             Error::NoValueFound(_) => "No value was found",
             Error::Overflow => "Value too high or too low",
             Error::ExpNotSupported => "Exponential notation not supported (i.e. not 2.3e4)",
+            Error::DivisionByZero(_) => "A value was divided by zero",
+            Error::UnexpectedToken(_) => "Something unexpected was found",
+            Error::UnexpectedEnd => "The input ended too early",
+            Error::InvalidOperation(_) => "Values were combined in an invalid way",
+            Error::NestingTooDeep => "Too many nested parentheses",
+            Error::InvalidRange(_) => "A range was invalid or empty",
+            Error::TooShort { .. } => "The duration was too short",
+            Error::TooLong { .. } => "The duration was too long",
+            Error::UnitNotAllowed { .. } => "A unit was used that is not allowed",
+            Error::ZeroNotAllowed => "The duration was zero",
+            Error::InvalidFormat(_) => "The input did not follow the format",
+            Error::InputTooLong { .. } => "The input was too long",
+            Error::TooManyComponents { .. } => "The input had too many values",
+            Error::AmbiguousUnit { .. } => "A unit was used that is ambiguous",
+        }
+    }
+}
+
+impl Error {
+    /// The name of the variant, like `"UnknownUnit"`, for machine-readable output.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Error::ParseInt(_) => "ParseInt",
+            Error::UnknownUnit(_) => "UnknownUnit",
+            Error::OutOfBounds(_) => "OutOfBounds",
+            Error::Overflow => "Overflow",
+            Error::ExpNotSupported => "ExpNotSupported",
+            Error::NoUnitFound(_) => "NoUnitFound",
+            Error::NoValueFound(_) => "NoValueFound",
+            Error::DivisionByZero(_) => "DivisionByZero",
+            Error::UnexpectedToken(_) => "UnexpectedToken",
+            Error::UnexpectedEnd => "UnexpectedEnd",
+            Error::InvalidOperation(_) => "InvalidOperation",
+            Error::NestingTooDeep => "NestingTooDeep",
+            Error::InvalidRange(_) => "InvalidRange",
+            Error::TooShort { .. } => "TooShort",
+            Error::TooLong { .. } => "TooLong",
+            Error::UnitNotAllowed { .. } => "UnitNotAllowed",
+            Error::ZeroNotAllowed => "ZeroNotAllowed",
+            Error::InvalidFormat(_) => "InvalidFormat",
+            Error::InputTooLong { .. } => "InputTooLong",
+            Error::TooManyComponents { .. } => "TooManyComponents",
+            Error::AmbiguousUnit { .. } => "AmbiguousUnit",
         }
     }
 }
 
+/// An error, with the byte range of the input that caused it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SpannedError {
+    pub error: Error,
+    pub span: Range<usize>,
+}
+
+impl SpannedError {
+    pub(crate) fn new(error: Error, span: Range<usize>) -> Self {
+        SpannedError { error, span }
+    }
+}
+
+impl fmt::Display for SpannedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} (at {}..{})",
+            self.error, self.span.start, self.span.end
+        )
+    }
+}
+
+impl ErrorTrait for SpannedError {
+    fn source(&self) -> Option<&(dyn ErrorTrait + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<SpannedError> for Error {
+    fn from(spanned: SpannedError) -> Self {
+        spanned.error
+    }
+}
+
+/// A unit of time that a value can be expressed in.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Unit {
+    Nanosecond,
+    Microsecond,
+    Millisecond,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+    /// Two weeks.
+    Fortnight,
+    /// Three months, a quarter of a year.
+    Quarter,
+    /// Ten years.
+    Decade,
+    /// A hundred years.
+    Century,
+    /// 10 milliseconds, the clock tick of the Linux kernel as reported to programs (`USER_HZ`).
+    Jiffy,
+    /// A thousand seconds.
+    Kilosecond,
+    /// A million seconds.
+    Megasecond,
+    /// A billion seconds.
+    Gigasecond,
+}
+
+impl Unit {
+    /// The number of nanoseconds in one of this unit.
+    pub(crate) fn nanoseconds(self) -> i64 {
+        match self {
+            Unit::Nanosecond => 1,
+            Unit::Microsecond => 1_000,
+            Unit::Millisecond => 1_000_000,
+            Unit::Second => 1_000_000_000,
+            Unit::Minute => 60_000_000_000,
+            Unit::Hour => 3_600_000_000_000,
+            Unit::Day => 86_400_000_000_000,
+            Unit::Week => 604_800_000_000_000,
+            Unit::Month => 2_629_746_000_000_000,
+            Unit::Year => 31_556_952_000_000_000,
+            Unit::Fortnight => 1_209_600_000_000_000,
+            Unit::Quarter => 7_889_238_000_000_000,
+            Unit::Decade => 315_569_520_000_000_000,
+            Unit::Century => 3_155_695_200_000_000_000,
+            Unit::Jiffy => 10_000_000,
+            Unit::Kilosecond => 1_000_000_000_000,
+            Unit::Megasecond => 1_000_000_000_000_000,
+            Unit::Gigasecond => 1_000_000_000_000_000_000,
+        }
+    }
+}
+
+/// How long years and months are, for [`Options::calendar`](../options/struct.Options.html#method.calendar).
+///
+/// This also applies to the units made of them: quarters are three months, and decades and
+/// centuries are ten and a hundred years.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub enum Calendar {
+    /// The average over 400 years of the Gregorian calendar: a year is 365.2425 days,
+    /// and a month is one twelfth of that. This is the default.
+    #[default]
+    Gregorian,
+    /// The Julian year used in astronomy, of 365.25 days, and a month of one twelfth of that.
+    Julian,
+    /// A year of 365 days and a month of 30 days, as is common in finance.
+    Fixed,
+    /// No years and months at all, since their length depends on the date. They are rejected
+    /// with [`Error::UnitNotAllowed`](enum.Error.html#variant.UnitNotAllowed).
+    Reject,
+}
+
+impl Calendar {
+    /// The number of seconds in a year and in a month.
+    fn year_and_month(self) -> (i64, i64) {
+        match self {
+            // With `Reject`, calendar units are rejected before they are summed.
+            Calendar::Gregorian | Calendar::Reject => (31_556_952, 2_629_746),
+            Calendar::Julian => (31_557_600, 2_629_800),
+            Calendar::Fixed => (31_536_000, 2_592_000),
+        }
+    }
+
+    /// The number of nanoseconds in one `unit`.
+    pub(crate) fn nanoseconds(self, unit: Unit) -> i64 {
+        let (year, month) = self.year_and_month();
+        match unit {
+            Unit::Month => month * 1_000_000_000,
+            Unit::Quarter => 3 * month * 1_000_000_000,
+            Unit::Year => year * 1_000_000_000,
+            Unit::Decade => 10 * year * 1_000_000_000,
+            Unit::Century => 100 * year * 1_000_000_000,
+            unit => unit.nanoseconds(),
+        }
+    }
+
+    /// Whether `unit` may be used, which is all units except for `Reject`.
+    pub(crate) fn allows(self, unit: Unit) -> bool {
+        self != Calendar::Reject
+            || !matches!(
+                unit,
+                Unit::Month | Unit::Quarter | Unit::Year | Unit::Decade | Unit::Century
+            )
+    }
+}
+
 /// A `ProtoDuration` is a duration with arbitrarily large fields.
 /// It can be conditionally converted into a normal Duration, if the fields are small enough.
 #[derive(Default)]
-struct ProtoDuration {
+pub(crate) struct ProtoDuration {
     /// The number of nanoseconds in the `ProtoDuration`. May be negative.
-    nanoseconds: i64,
+    /// Decimal values are added here, so this is wider than the other fields.
+    nanoseconds: i128,
     /// The number of microseconds in the `ProtoDuration`. May be negative.
     microseconds: i64,
     /// The number of milliseconds in the `ProtoDuration`. May be negative.
@@ -96,109 +392,219 @@ struct ProtoDuration {
 }
 
 impl ProtoDuration {
+    /// Add an integer number of `unit`s, failing if the number of `unit`s no longer fits.
+    pub(crate) fn add(&mut self, unit: Unit, int: i64) -> Result<(), Error> {
+        // Units without a field of their own are a multiple of another unit.
+        let (field, factor) = match unit {
+            Unit::Nanosecond => return self.add_nanoseconds(int.into()),
+            Unit::Microsecond => (&mut self.microseconds, 1),
+            Unit::Millisecond => (&mut self.milliseconds, 1),
+            Unit::Second => (&mut self.seconds, 1),
+            Unit::Minute => (&mut self.minutes, 1),
+            Unit::Hour => (&mut self.hours, 1),
+            Unit::Day => (&mut self.days, 1),
+            Unit::Week => (&mut self.weeks, 1),
+            Unit::Month => (&mut self.months, 1),
+            Unit::Year => (&mut self.years, 1),
+            Unit::Fortnight => (&mut self.weeks, 2),
+            Unit::Quarter => (&mut self.months, 3),
+            Unit::Decade => (&mut self.years, 10),
+            Unit::Century => (&mut self.years, 100),
+            Unit::Jiffy => (&mut self.milliseconds, 10),
+            Unit::Kilosecond => (&mut self.seconds, 1_000),
+            Unit::Megasecond => (&mut self.seconds, 1_000_000),
+            Unit::Gigasecond => (&mut self.seconds, 1_000_000_000),
+        };
+        *field = int
+            .checked_mul(factor)
+            .and_then(|int| field.checked_add(int))
+            .ok_or(Error::Overflow)?;
+        Ok(())
+    }
+
+    /// Add a number of nanoseconds, which may be more than fit in an `i64`.
+    pub(crate) fn add_nanoseconds(&mut self, nanoseconds: i128) -> Result<(), Error> {
+        self.nanoseconds = self
+            .nanoseconds
+            .checked_add(nanoseconds)
+            .ok_or(Error::Overflow)?;
+        Ok(())
+    }
+
     /// Try to convert a `ProtoDuration` into a `Duration`.
     /// This may fail if the `ProtoDuration` is too long or it ends up having a negative total duration.
-    fn into_duration(self) -> Result<Duration, Error> {
+    pub(crate) fn into_duration(self, calendar: Calendar) -> Result<Duration, Error> {
+        let (year, month) = calendar.year_and_month();
+        // Only the nanoseconds, which decimals add to, can overflow in 128 bits.
         let mut nanoseconds =
-            self.nanoseconds + 1_000_i64 * self.microseconds + 1_000_000_i64 * self.milliseconds;
-        let mut seconds = self.seconds
-            + 60_i64 * self.minutes
-            + 3_600_i64 * self.hours
-            + 86_400_i64 * self.days
-            + 604_800_i64 * self.weeks
-            + 2_629_746_i64 * self.months
-            + 31_556_952_i64 * self.years;
-
-        seconds += &nanoseconds / 1_000_000_000;
+            1_000 * i128::from(self.microseconds) + 1_000_000 * i128::from(self.milliseconds);
+        nanoseconds = nanoseconds
+            .checked_add(self.nanoseconds)
+            .ok_or(Error::Overflow)?;
+        let mut seconds = i128::from(self.seconds)
+            + 60 * i128::from(self.minutes)
+            + 3_600 * i128::from(self.hours)
+            + 86_400 * i128::from(self.days)
+            + 604_800 * i128::from(self.weeks)
+            + i128::from(month) * i128::from(self.months)
+            + i128::from(year) * i128::from(self.years);
+
+        seconds += nanoseconds / 1_000_000_000;
         nanoseconds %= 1_000_000_000;
+        // Borrow a second for negative nanoseconds, as in "1 minute -0.5 seconds".
+        if nanoseconds < 0 {
+            seconds -= 1;
+            nanoseconds += 1_000_000_000;
+        }
 
         let seconds: u64 = seconds
             .try_into()
-            .map_err(|_| Error::OutOfBounds(seconds))?;
-        let nanoseconds: u32 = nanoseconds
-            .try_into()
-            .map_err(|_| Error::OutOfBounds(nanoseconds))?;
-
-        Ok(Duration::new(seconds, nanoseconds))
-    }
-}
-
-lazy_static! {
-    static ref NUMBER_RE: Regex = Regex::new(
-        r"(?x)
-        ^
-        [^0-9a-zA-Z_-]*     # any non-word characters, except '-' (for negatives - may add '.' for decimals)
-        (-?[0-9]+)             # a possible negative sign and some positive number of digits
-        [^0-9a-zA-Z_-]*     # more non-word characters
-        $"
-    )
-    .expect("Compiling a regex went wrong");
-}
-
-lazy_static! {
-    static ref DURATION_RE: Regex = Regex::new(
-        r"(?x)
-        (?P<int>[-]?[0-9]+)            # the integer part
-        \.?(?:(?P<dec>[0-9]+))?        # an optional decimal part
-                                       # note: the previous part will eat any decimals
-                                       # if there's no decimal point.
-                                       # This means we'll always have the decimal point if this
-                                       # section matches at all.
-        (?:e(?P<exp>[-+]?[0-9]+))?     # an optional exponent
-        (?:
-            [^0-9a-zA-Z_]*             # some amount of junk (non word characters)
-            (?P<unit>[0-9a-zA-Z_&&[^0-9]]+)  # a word with no digits
-        )?
-        ",
-    )
-    .expect("Compiling a regex went wrong");
-}
-
-/// Convert some unit abbreviations to their full form.
+            .map_err(|_| match i64::try_from(seconds) {
+                Ok(seconds) => Error::OutOfBounds(seconds),
+                Err(_) => Error::Overflow,
+            })?;
+        // Between 0 and 999_999_999, after borrowing.
+        Ok(Duration::new(seconds, nanoseconds as u32))
+    }
+}
+
+/// The length in bytes of the longest unit that [`parse_unit`] accepts, with some room to spare.
+pub(crate) const MAX_UNIT_LEN: usize = 16;
+
+/// `word` in lower case, written into `buffer` to avoid an allocation.
+/// Returns `None` if it does not fit, in which case it is too long to be a unit.
+pub(crate) fn lowercase<'a>(word: &str, buffer: &'a mut [u8; MAX_UNIT_LEN]) -> Option<&'a str> {
+    let mut len = 0;
+    for c in word.chars().flat_map(char::to_lowercase) {
+        let end = len + c.len_utf8();
+        c.encode_utf8(buffer.get_mut(len..end)?);
+        len = end;
+    }
+    ::std::str::from_utf8(&buffer[..len]).ok()
+}
+
+/// The units that are only accepted with their full name or a fixed abbreviation,
+/// not with any initial segment, so that for example "de" and "c" remain unknown.
+fn other_unit(unit_casefold: &str) -> Option<Unit> {
+    Some(match unit_casefold {
+        "fortnight" | "fortnights" => Unit::Fortnight,
+        "quarter" | "quarters" | "qtr" | "qtrs" => Unit::Quarter,
+        "decade" | "decades" => Unit::Decade,
+        "century" | "centuries" => Unit::Century,
+        "jiffy" | "jiffies" => Unit::Jiffy,
+        "ks" | "ksec" | "ksecs" | "kilosecond" | "kiloseconds" => Unit::Kilosecond,
+        "megasecond" | "megaseconds" => Unit::Megasecond,
+        "gs" | "gsec" | "gsecs" | "gigasecond" | "gigaseconds" => Unit::Gigasecond,
+        _ => return None,
+    })
+}
+
+/// Convert some unit abbreviations to their unit.
 /// See the [module level documentation](index.html) for more information about which abbreviations are accepted.
-// TODO: return an `enum`.
-fn parse_unit(unit: &str) -> &str {
-    let unit_casefold = unit.to_lowercase();
+pub(crate) fn parse_unit(unit: &str) -> Option<Unit> {
+    let mut buffer = [0; MAX_UNIT_LEN];
+    let unit_casefold = lowercase(unit, &mut buffer)?;
+
+    // Like "M" for months, the SI symbol "Ms" is case-sensitive, so "ms" is still milliseconds.
+    if unit == "Ms" {
+        return Some(Unit::Megasecond);
+    }
+    if let Some(unit) = other_unit(unit_casefold) {
+        return Some(unit);
+    }
 
     if unit_casefold.starts_with('n')
-        && ("nanoseconds".starts_with(&unit_casefold) || "nsecs".starts_with(&unit_casefold))
+        && ("nanoseconds".starts_with(unit_casefold) || "nsecs".starts_with(unit_casefold))
     {
-        "nanoseconds"
-    } else if unit_casefold.starts_with("mic") && "microseconds".starts_with(&unit_casefold)
-        || unit_casefold.starts_with('u') && "usecs".starts_with(&unit_casefold)
-        || unit_casefold.starts_with('μ') && "\u{3bc}secs".starts_with(&unit_casefold)
+        Some(Unit::Nanosecond)
+    } else if unit_casefold.starts_with("mic") && "microseconds".starts_with(unit_casefold)
+        || unit_casefold.starts_with('u') && "usecs".starts_with(unit_casefold)
+        || unit_casefold.starts_with('μ') && "\u{3bc}secs".starts_with(unit_casefold)
     {
-        "microseconds"
-    } else if unit_casefold.starts_with("mil") && "milliseconds".starts_with(&unit_casefold)
-        || unit_casefold.starts_with("ms") && "msecs".starts_with(&unit_casefold)
+        Some(Unit::Microsecond)
+    } else if unit_casefold.starts_with("mil") && "milliseconds".starts_with(unit_casefold)
+        || unit_casefold.starts_with("ms") && "msecs".starts_with(unit_casefold)
     {
-        "milliseconds"
+        Some(Unit::Millisecond)
     } else if unit_casefold.starts_with('s')
-        && ("seconds".starts_with(&unit_casefold) || "secs".starts_with(&unit_casefold))
+        && ("seconds".starts_with(unit_casefold) || "secs".starts_with(unit_casefold))
     {
-        "seconds"
+        Some(Unit::Second)
     } else if (unit_casefold.starts_with("min") || unit.starts_with('m'))
-        && ("minutes".starts_with(&unit_casefold) || "mins".starts_with(&unit_casefold))
+        && ("minutes".starts_with(unit_casefold) || "mins".starts_with(unit_casefold))
     {
-        "minutes"
+        Some(Unit::Minute)
     } else if unit_casefold.starts_with('h')
-        && ("hours".starts_with(&unit_casefold) || "hrs".starts_with(&unit_casefold))
+        && ("hours".starts_with(unit_casefold) || "hrs".starts_with(unit_casefold))
     {
-        "hours"
-    } else if unit_casefold.starts_with('d') && "days".starts_with(&unit_casefold) {
-        "days"
-    } else if unit_casefold.starts_with('w') && "weeks".starts_with(&unit_casefold) {
-        "weeks"
+        Some(Unit::Hour)
+    } else if unit_casefold.starts_with('d') && "days".starts_with(unit_casefold) {
+        Some(Unit::Day)
+    } else if unit_casefold.starts_with('w') && "weeks".starts_with(unit_casefold) {
+        Some(Unit::Week)
     } else if (unit_casefold.starts_with("mo") || unit.starts_with('M'))
-        && "months".starts_with(&unit_casefold)
+        && "months".starts_with(unit_casefold)
     {
-        "months"
+        Some(Unit::Month)
     } else if unit_casefold.starts_with('y')
-        && ("years".starts_with(&unit_casefold) || "yrs".starts_with(&unit_casefold))
+        && ("years".starts_with(unit_casefold) || "yrs".starts_with(unit_casefold))
     {
-        "years"
+        Some(Unit::Year)
     } else {
-        unit
+        None
+    }
+}
+
+/// How the case of a unit matters, for [`Options::case_policy`](../options/struct.Options.html#method.case_policy).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub enum CasePolicy {
+    /// Units are case-insensitive, except that `"M"` is months while `"m"` is minutes, and
+    /// `"Ms"` is megaseconds while `"ms"` is milliseconds. This is the default.
+    #[default]
+    Mixed,
+    /// Units are case-insensitive, so `"M"` is minutes and `"Ms"` is milliseconds.
+    Insensitive,
+    /// Units must be in lower case, except for the symbols `"M"`, `"Ms"` and `"Gs"`.
+    /// For example, `"MIN"` and `"Hours"` are unknown units.
+    Sensitive,
+    /// Like `Mixed`, but abbreviations that mean different units depending on their case are
+    /// rejected with [`Error::AmbiguousUnit`](enum.Error.html#variant.AmbiguousUnit).
+    /// These are `"m"` and `"M"`, and `"ms"` and `"Ms"`.
+    RejectAmbiguous,
+}
+
+impl CasePolicy {
+    /// Find the unit for a word, or `None` if it is unknown or not allowed by this policy.
+    pub(crate) fn resolve(self, word: &str) -> Option<Unit> {
+        match self {
+            CasePolicy::Mixed => parse_unit(word),
+            CasePolicy::Insensitive => parse_unit(lowercase(word, &mut [0; MAX_UNIT_LEN])?),
+            CasePolicy::Sensitive => match word {
+                "M" | "Ms" | "Gs" => parse_unit(word),
+                _ if word.chars().any(char::is_uppercase) => None,
+                _ => parse_unit(word),
+            },
+            CasePolicy::RejectAmbiguous => match case_candidates(word) {
+                Some(_) => None,
+                None => parse_unit(word),
+            },
+        }
+    }
+}
+
+/// The units that `word` means in lower case and with a capital, if these differ,
+/// like minutes and months for `"m"`.
+pub(crate) fn case_candidates(word: &str) -> Option<[Unit; 2]> {
+    let mut buffer = [0; MAX_UNIT_LEN];
+    let lower = lowercase(word, &mut buffer)?;
+    let len = lower.len();
+    let lower_unit = parse_unit(lower)?;
+    let mut capital = buffer;
+    capital[0].make_ascii_uppercase();
+    let capital = ::std::str::from_utf8(&capital[..len]).ok()?;
+    match parse_unit(capital)? {
+        capital_unit if capital_unit != lower_unit => Some([lower_unit, capital_unit]),
+        _ => None,
     }
 }
 
@@ -206,102 +612,469 @@ fn parse_unit(unit: &str) -> &str {
 ///
 /// See the [module level documentation](index.html) for more.
 pub fn parse(input: &str) -> Result<Duration, Error> {
-    if let Some(int) = NUMBER_RE.captures(input) {
-        // This means it's just a value
-        // Since the regex matched, the first group exists, so we can unwrap.
-        let txt = int.get(1).unwrap().as_str();
-        let seconds = txt
-            .parse::<i64>()
-            .map_err(|_| Error::ParseInt(txt.to_owned()))?;
-        Ok(Duration::new(
-            seconds.try_into().map_err(|_| Error::Overflow)?,
-            0,
-        ))
-    } else if DURATION_RE.is_match(input) {
-        // This means we have at least one "unit" (or plain word) and one value.
-        let mut duration = ProtoDuration::default();
-        for capture in DURATION_RE.captures_iter(input) {
-            if capture.name("exp").is_some() {
-                return Err(Error::ExpNotSupported);
+    parse_with_units(input, ParseConfig::default(), parse_unit)
+}
+
+/// An exact value of `numerator / denominator`, such as 1.5 (15 / 10) or ½ (1 / 2).
+/// The denominator is always positive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Value {
+    pub numerator: i128,
+    pub denominator: i128,
+}
+
+/// The greatest common divisor, or `None` if it is 2^127, which does not fit.
+pub(crate) fn gcd(mut a: i128, mut b: i128) -> Option<i128> {
+    while b != 0 {
+        // `i128::MIN % -1` is 0, but `%` overflows computing it.
+        (a, b) = (b, a.wrapping_rem(b));
+    }
+    a.checked_abs()
+}
+
+impl Value {
+    pub fn integer(int: i64) -> Self {
+        Value {
+            numerator: int.into(),
+            denominator: 1,
+        }
+    }
+
+    /// A fraction, or `None` if the denominator is zero or the fraction overflows.
+    pub fn ratio(numerator: i128, denominator: i128) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        let divisor = gcd(numerator, denominator)? * denominator.signum();
+        Some(Value {
+            numerator: numerator.checked_div(divisor)?,
+            denominator: denominator.checked_div(divisor)?,
+        })
+    }
+
+    /// Build a value from the integer and decimal parts, where `exp` is the number of decimals.
+    /// The decimals have the sign of the value, which `int` does not have if it is `-0`.
+    fn decimal(int: i64, dec: i64, exp: u32, negative: bool) -> Result<Self, Error> {
+        let scale = 10_i128.checked_pow(exp).ok_or(Error::Overflow)?;
+        let dec = if negative {
+            -i128::from(dec)
+        } else {
+            dec.into()
+        };
+        let numerator = i128::from(int)
+            .checked_mul(scale)
+            .and_then(|boosted| boosted.checked_add(dec))
+            .ok_or(Error::Overflow)?;
+        Ok(Value {
+            numerator,
+            denominator: scale,
+        })
+    }
+
+    pub fn checked_add(self, other: Value) -> Option<Value> {
+        let numerator = self
+            .numerator
+            .checked_mul(other.denominator)?
+            .checked_add(other.numerator.checked_mul(self.denominator)?)?;
+        Value::ratio(numerator, self.denominator.checked_mul(other.denominator)?)
+    }
+
+    pub fn checked_mul(self, other: Value) -> Option<Value> {
+        Value::ratio(
+            self.numerator.checked_mul(other.numerator)?,
+            self.denominator.checked_mul(other.denominator)?,
+        )
+    }
+
+    /// Whether this is a whole number, e.g. 3 but not 3.5.
+    pub fn is_integer(self) -> bool {
+        self.denominator == 1
+    }
+
+    /// This value times `unit` of `calendar`, in whole nanoseconds, rounding towards zero.
+    pub fn nanoseconds(self, unit: Unit, calendar: Calendar) -> Option<i128> {
+        self.numerator
+            .checked_mul(calendar.nanoseconds(unit).into())
+            .map(|boosted| boosted / self.denominator)
+    }
+}
+
+/// The byte range of the input that an error is about, or all of the input.
+#[cfg(any(feature = "ffi", feature = "wasm"))]
+pub(crate) fn error_span(input: &str, error: &Error) -> Range<usize> {
+    let text = match error {
+        Error::ParseInt(text)
+        | Error::UnknownUnit(text)
+        | Error::NoUnitFound(text)
+        | Error::DivisionByZero(text) => text,
+        _ => return 0..input.len(),
+    };
+    // Prefer a whole token, so that the unit "m" is not found inside "min".
+    let token = Lexer::new(input, LexerConfig::default()).find(|token| token.text(input) == text);
+    match (token, input.find(text.as_str())) {
+        (Some(token), _) => token.start..token.end,
+        (None, Some(start)) => start..start + text.len(),
+        (None, None) => 0..input.len(),
+    }
+}
+
+/// Convert a number of nanoseconds into a `Duration`, failing like
+/// [`ProtoDuration::into_duration`] if it is negative or too long.
+pub(crate) fn duration_from_nanoseconds(nanoseconds: i128) -> Result<Duration, Error> {
+    let seconds = nanoseconds / 1_000_000_000;
+    let nanoseconds = nanoseconds % 1_000_000_000;
+    let seconds: u64 = seconds
+        .try_into()
+        .map_err(|_| match i64::try_from(seconds) {
+            Ok(seconds) => Error::OutOfBounds(seconds),
+            Err(_) => Error::Overflow,
+        })?;
+    let nanoseconds: u32 = nanoseconds
+        .try_into()
+        .map_err(|_| Error::OutOfBounds(nanoseconds as i64))?;
+    Ok(Duration::new(seconds, nanoseconds))
+}
+
+/// A value, with the unit that follows it if there is one.
+pub(crate) struct Component {
+    pub number: Token,
+    pub unit: Option<Token>,
+}
+
+/// An iterator over the components of an input.
+/// Words that do not follow a value are skipped.
+pub(crate) struct Components<I: Iterator<Item = Token>> {
+    pub tokens: Peekable<I>,
+}
+
+impl<I: Iterator<Item = Token>> Iterator for Components<I> {
+    type Item = Component;
+
+    fn next(&mut self) -> Option<Component> {
+        let number = self.tokens.find(|token| {
+            matches!(
+                token.kind,
+                TokenKind::Number { .. } | TokenKind::WordNumber(_)
+            )
+        })?;
+        if let Some(Token {
+            kind: TokenKind::Junk,
+            ..
+        }) = self.tokens.peek()
+        {
+            self.tokens.next();
+        }
+        let unit = match self.tokens.peek() {
+            Some(token) if token.kind == TokenKind::Word => self.tokens.next(),
+            _ => None,
+        };
+        Some(Component { number, unit })
+    }
+}
+
+/// Whether the input is a single integer surrounded by junk, so far.
+/// Such an input is a number of seconds.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum LoneInteger {
+    /// Only junk and this integer, if any, have been seen.
+    Possible(Option<Token>),
+    Impossible,
+}
+
+impl LoneInteger {
+    pub(crate) fn observe(self, input: &str, token: &Token) -> Self {
+        match (self, token.kind) {
+            (
+                LoneInteger::Possible(None),
+                TokenKind::Number {
+                    fraction: None,
+                    denominator: None,
+                    vulgar: None,
+                    exponent: false,
+                    ..
+                },
+            ) => LoneInteger::Possible(Some(*token)),
+            (LoneInteger::Possible(integer), TokenKind::Junk)
+                if !token.text(input).contains('-') =>
+            {
+                LoneInteger::Possible(integer)
             }
-            match (
-                capture.name("int"),
-                capture.name("dec"),
-                capture.name("unit"),
-            ) {
-                // capture.get(0) is *always* the actual match, so unwrapping causes no problems
-                (.., None) => {
-                    return Err(Error::NoUnitFound(
-                        capture.get(0).unwrap().as_str().to_owned(),
-                    ))
+            _ => LoneInteger::Impossible,
+        }
+    }
+
+    /// The integer, if the whole input has been seen and it is a lone integer.
+    pub(crate) fn integer(self) -> Option<Token> {
+        match self {
+            LoneInteger::Possible(integer) => integer,
+            LoneInteger::Impossible => None,
+        }
+    }
+}
+
+/// The duration of a lone integer, which is a number of seconds.
+fn lone_seconds(input: &str, integer: Token, config: ParseConfig) -> Result<Duration, Error> {
+    if !config.seconds_allowed {
+        return Err(Error::UnitNotAllowed {
+            word: integer.text(input).to_owned(),
+            unit: Unit::Second,
+        });
+    }
+    let seconds = number_value(input, integer)?.numerator;
+    Ok(Duration::new(
+        seconds.try_into().map_err(|_| Error::Overflow)?,
+        0,
+    ))
+}
+
+/// Parse an integer that may contain group separators.
+fn parse_digits(txt: &str) -> Result<i64, Error> {
+    let error = || Error::ParseInt(txt.to_owned());
+    let (negative, digits) = match txt.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, txt),
+    };
+    let mut value: i64 = 0;
+    let mut found = false;
+    for digit in digits.bytes().filter(u8::is_ascii_digit) {
+        let digit = i64::from(digit - b'0');
+        // Negative values are built downwards, so that `i64::MIN` fits.
+        value = value
+            .checked_mul(10)
+            .and_then(|value| {
+                if negative {
+                    value.checked_sub(digit)
+                } else {
+                    value.checked_add(digit)
                 }
-                (None, ..) => {
-                    return Err(Error::NoValueFound(
-                        capture.get(0).unwrap().as_str().to_owned(),
-                    ))
+            })
+            .ok_or_else(error)?;
+        found = true;
+    }
+    if found {
+        Ok(value)
+    } else {
+        Err(error())
+    }
+}
+
+/// The value of a number token.
+pub(crate) fn number_value(input: &str, number: Token) -> Result<Value, Error> {
+    match number.kind {
+        TokenKind::Number {
+            int_end,
+            fraction,
+            numerator,
+            denominator,
+            vulgar,
+            ..
+        } => {
+            let txt = &input[number.start..int_end];
+            let int = match txt {
+                // Fractions like "½" or "-½" have no integer part.
+                "" | "-" => 0,
+                _ => parse_digits(txt)?,
+            };
+            if let Some((start, end)) = fraction {
+                let txt = &input[start..end];
+                let exp: u32 = txt
+                    .bytes()
+                    .filter(u8::is_ascii_digit)
+                    .count()
+                    .try_into()
+                    .map_err(|_| Error::Overflow)?;
+                let negative = input[number.start..int_end].starts_with('-');
+                Value::decimal(int, parse_digits(txt)?, exp, negative)
+            } else if let Some((start, end)) = denominator {
+                let denominator = parse_digits(&input[start..end])?;
+                if denominator == 0 {
+                    return Err(Error::DivisionByZero(number.text(input).to_owned()));
                 }
-                (Some(int), None, Some(unit)) => {
-                    let txt = int.as_str();
-                    let int = txt
-                        .parse::<i64>()
-                        .map_err(|_| Error::ParseInt(txt.to_owned()))?;
-
-                    match parse_unit(unit.as_str()) {
-                        "nanoseconds" => duration.nanoseconds += int,
-                        "microseconds" => duration.microseconds += int,
-                        "milliseconds" => duration.milliseconds += int,
-                        "seconds" => duration.seconds += int,
-                        "minutes" => duration.minutes += int,
-                        "hours" => duration.hours += int,
-                        "days" => duration.days += int,
-                        "weeks" => duration.weeks += int,
-                        "months" => duration.months += int,
-                        "years" => duration.years += int,
-                        s => return Err(Error::UnknownUnit(s.to_owned())),
+                match numerator {
+                    // A mixed number like "1 1/2", where the fraction has the sign of the value.
+                    Some((start, end)) => {
+                        let numerator = parse_digits(&input[start..end])?;
+                        let sign = if txt.starts_with('-') { -1 } else { 1 };
+                        Value::ratio(sign * i128::from(numerator), denominator.into())
+                            .and_then(|fraction| Value::integer(int).checked_add(fraction))
+                            .ok_or(Error::Overflow)
                     }
+                    None => Value::ratio(int.into(), denominator.into()).ok_or(Error::Overflow),
                 }
-                (Some(int), Some(dec), Some(unit)) => {
-                    let txt = int.as_str();
-                    let int = txt
-                        .parse::<i64>()
-                        .map_err(|_| Error::ParseInt(txt.to_owned()))?;
-
-                    let exp: u32 = dec
-                        .as_str()
-                        .len()
-                        .try_into()
-                        .expect("number of decimals too large");
-
-                    let txt = dec.as_str();
-                    let dec = txt
-                        .parse::<i64>()
-                        .map_err(|_| Error::ParseInt(txt.to_owned()))?;
-
-                    // boosted_int is value * 10^exp * unit
-                    let mut boosted_int = int * 10_i64.pow(exp) + dec;
-
-                    // boosted_int is now value * 10^exp * nanoseconds
-                    match parse_unit(unit.as_str()) {
-                        "nanoseconds" => {}
-                        "microseconds" => boosted_int *= 1_000_i64,
-                        "milliseconds" => boosted_int *= 1_000_000_i64,
-                        "seconds" => boosted_int *= 1_000_000_000_i64,
-                        "minutes" => boosted_int *= 60_000_000_000_i64,
-                        "hours" => boosted_int *= 3_600_000_000_000_i64,
-                        "days" => boosted_int *= 86_400_000_000_000_i64,
-                        "weeks" => boosted_int *= 604_800_000_000_000_i64,
-                        "months" => boosted_int *= 2_629_746_000_000_000_i64,
-                        "years" => boosted_int *= 31_556_952_000_000_000_i64,
-                        s => return Err(Error::UnknownUnit(s.to_owned())),
-                    }
+            } else if let Some((numerator, denominator)) = vulgar {
+                let sign = if txt.starts_with('-') { -1 } else { 1 };
+                Value::integer(int)
+                    .checked_add(Value {
+                        numerator: sign * i128::from(numerator),
+                        denominator: denominator.into(),
+                    })
+                    .ok_or(Error::Overflow)
+            } else {
+                Ok(Value::integer(int))
+            }
+        }
+        TokenKind::WordNumber(value) => Ok(value),
+        TokenKind::Word | TokenKind::Junk => unreachable!("not a number"),
+    }
+}
 
-                    // boosted_int is now value * nanoseconds (rounding down)
-                    boosted_int /= 10_i64.pow(exp);
-                    duration.nanoseconds += boosted_int;
-                }
+/// Memory for the tokens of an input, which a [`Parser`](../parser/struct.Parser.html) keeps
+/// between inputs. It is only used with natural language or in strict mode.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Scratch {
+    tokens: Vec<Token>,
+    words: Vec<Token>,
+}
+
+/// How the parser reads the input, on top of how the lexer splits it.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ParseConfig<'a> {
+    /// How the input is split into numbers, words and junk.
+    pub lexer: LexerConfig<'a>,
+    /// Whether English number words like "twenty" or "half" are values, see the `words` module.
+    pub natural_language: bool,
+    /// Whether everything except whitespace must be a value or its unit.
+    pub strict: bool,
+    /// The most values that an input may have, if there is a maximum.
+    pub max_components: Option<usize>,
+    /// How long years and months are.
+    pub calendar: Calendar,
+    /// Whether seconds are allowed, which a lone integer like "15" is read as.
+    pub seconds_allowed: bool,
+}
+
+impl Default for ParseConfig<'_> {
+    fn default() -> Self {
+        ParseConfig {
+            lexer: LexerConfig::default(),
+            natural_language: false,
+            strict: false,
+            max_components: None,
+            calendar: Calendar::Gregorian,
+            seconds_allowed: true,
+        }
+    }
+}
+
+/// The shared implementation of [`parse`](fn.parse.html) and the configurable parsers.
+/// `config` determines how the input is split into values and units,
+/// and `resolve` maps a unit word to a `Unit`.
+pub(crate) fn parse_with_units(
+    input: &str,
+    config: ParseConfig,
+    resolve: impl Fn(&str) -> Option<Unit>,
+) -> Result<Duration, Error> {
+    parse_with_scratch(input, config, resolve, &mut Scratch::default())
+}
+
+/// [`parse_with_units`], reusing the memory in `scratch`.
+///
+/// Without natural language and strict mode, this reads the input once and does not allocate,
+/// unless there is an error.
+pub(crate) fn parse_with_scratch(
+    input: &str,
+    config: ParseConfig,
+    resolve: impl Fn(&str) -> Option<Unit>,
+    scratch: &mut Scratch,
+) -> Result<Duration, Error> {
+    let lexer = Lexer::new(input, config.lexer);
+    if !config.natural_language && !config.strict {
+        return sum_components(input, lexer, resolve, true, config);
+    }
+    scratch.tokens.clear();
+    scratch.tokens.extend(lexer);
+    let tokens = if config.natural_language {
+        // A lone integer is found before number words, which may turn words into junk.
+        let lone = scratch
+            .tokens
+            .iter()
+            .fold(LoneInteger::Possible(None), |lone, token| {
+                lone.observe(input, token)
+            });
+        if let (false, Some(integer)) = (config.strict, lone.integer()) {
+            return lone_seconds(input, integer, config);
+        }
+        words::number_words_into(input, &scratch.tokens, &resolve, &mut scratch.words)?;
+        &scratch.words
+    } else {
+        &scratch.tokens
+    };
+    if config.strict {
+        check_strict(input, tokens)?;
+    }
+    sum_components(input, tokens.iter().copied(), resolve, false, config)
+}
+
+/// Check that every token is whitespace, a value, or a word directly after a value.
+fn check_strict(input: &str, tokens: &[Token]) -> Result<(), Error> {
+    let mut after_value = false;
+    for token in tokens {
+        let text = token.text(input);
+        match token.kind {
+            TokenKind::Number { .. } | TokenKind::WordNumber(_) => after_value = true,
+            TokenKind::Word if after_value => after_value = false,
+            TokenKind::Junk if text.chars().all(char::is_whitespace) => {}
+            _ => return Err(Error::UnexpectedToken(text.trim().to_owned())),
+        }
+    }
+    Ok(())
+}
+
+fn sum_components(
+    input: &str,
+    tokens: impl Iterator<Item = Token>,
+    resolve: impl Fn(&str) -> Option<Unit>,
+    lone_integer: bool,
+    config: ParseConfig,
+) -> Result<Duration, Error> {
+    let lone = Cell::new(if lone_integer {
+        LoneInteger::Possible(None)
+    } else {
+        LoneInteger::Impossible
+    });
+    let mut components = Components {
+        tokens: tokens
+            .inspect(|token| lone.set(lone.get().observe(input, token)))
+            .peekable(),
+    };
+    let mut duration = ProtoDuration::default();
+    let mut count = 0;
+    for Component { number, unit } in components.by_ref() {
+        count += 1;
+        if let Some(max) = config.max_components.filter(|&max| count > max) {
+            return Err(Error::TooManyComponents { max });
+        }
+        if let TokenKind::Number { exponent: true, .. } = number.kind {
+            return Err(Error::ExpNotSupported);
+        }
+        let unit = match unit {
+            Some(unit) => unit.text(input),
+            None => {
+                // The rest of the input decides whether this is a lone integer.
+                components.tokens.by_ref().for_each(drop);
+                return match lone.get().integer() {
+                    Some(integer) => lone_seconds(input, integer, config),
+                    None => Err(Error::NoUnitFound(number.text(input).to_owned())),
+                };
             }
+        };
+        let value = number_value(input, number)?;
+        let resolved = match resolve(unit) {
+            Some(resolved) => resolved,
+            None => return Err(Error::UnknownUnit(unit.to_owned())),
+        };
+        if value.is_integer() {
+            let int = value.numerator.try_into().map_err(|_| Error::Overflow)?;
+            duration.add(resolved, int)?;
+        } else {
+            let nanoseconds = value
+                .nanoseconds(resolved, config.calendar)
+                .ok_or(Error::Overflow)?;
+            duration.add_nanoseconds(nanoseconds)?;
         }
-        duration.into_duration()
+    }
+    if count > 0 {
+        duration.into_duration(config.calendar)
     } else {
         // Just a unit or nothing at all
         Err(Error::NoValueFound(input.to_owned()))
@@ -0,0 +1,227 @@
+use ::std::io::{self, BufRead};
+use ::std::time::Duration;
+
+use crate::options::Options;
+use crate::parse::{parse_with_scratch, Error, Scratch};
+
+/// A parser with fixed [`Options`](../options/struct.Options.html), for parsing many inputs.
+///
+/// A `Parser` accepts the same inputs as [`parse_with`](../options/fn.parse_with.html),
+/// but keeps the memory it needs for one input around for the next, so that parsing does not
+/// allocate, even with natural language or strict mode.
+///
+/// ```
+/// use ::parse_duration0::{Options, Parser};
+/// use ::std::time::Duration;
+///
+/// let mut parser = Parser::new(Options::new().natural_language(true));
+/// assert_eq!(parser.parse("twenty minutes"), Ok(Duration::new(1_200, 0)));
+///
+/// let durations: Vec<_> = parser.parse_many(["1 hour", "half a day"]).collect();
+/// assert_eq!(durations, vec![Ok(Duration::new(3_600, 0)), Ok(Duration::new(43_200, 0))]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Parser {
+    options: Options,
+    scratch: Scratch,
+}
+
+impl Parser {
+    /// A parser with the given options.
+    pub fn new(options: Options) -> Self {
+        Parser {
+            options,
+            scratch: Scratch::default(),
+        }
+    }
+
+    /// The options of this parser.
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    /// Parse a string into a duration object, like [`parse_with`](../options/fn.parse_with.html).
+    pub fn parse(&mut self, input: &str) -> Result<Duration, Error> {
+        let options = &self.options;
+        options.check_length(input)?;
+        parse_with_scratch(
+            input,
+            options.parse_config(),
+            |word| options.resolve(word),
+            &mut self.scratch,
+        )
+        .map_err(|error| options.check_unit(error))
+        .and_then(|value| options.check_bounds(value))
+    }
+
+    /// Parse each of `inputs`, in order.
+    ///
+    /// An error in one input does not stop the others from being parsed.
+    pub fn parse_many<I>(&mut self, inputs: I) -> ParseMany<'_, I::IntoIter>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        ParseMany {
+            parser: self,
+            inputs: inputs.into_iter(),
+        }
+    }
+
+    /// Parse each line of `reader`, for example a file with one duration per line.
+    ///
+    /// This yields the line number, starting at 1, with the result for that line.
+    /// The memory for a line is reused for the next, and the line ending, `\n` or `\r\n`,
+    /// is not part of the input. Use [`ParseLines::column`](struct.ParseLines.html#method.column)
+    /// to parse one column of each line instead, like in a CSV file.
+    ///
+    /// A line that can not be read, or is not valid UTF-8, is an `io::Error`. Reading continues
+    /// with the next line after invalid UTF-8.
+    ///
+    /// ```
+    /// use ::parse_duration0::{Error, Parser};
+    /// use ::std::time::Duration;
+    ///
+    /// let input = "1 hour\n90s\r\n\n";
+    /// let mut parser = Parser::default();
+    /// let results: Vec<_> = parser.parse_lines(input.as_bytes()).collect::<Result<_, _>>()?;
+    /// assert_eq!(
+    ///     results,
+    ///     vec![
+    ///         (1, Ok(Duration::new(3_600, 0))),
+    ///         (2, Ok(Duration::new(90, 0))),
+    ///         (3, Err(Error::NoValueFound(String::new()))),
+    ///     ]
+    /// );
+    /// # Ok::<(), ::std::io::Error>(())
+    /// ```
+    pub fn parse_lines<R: BufRead>(&mut self, reader: R) -> ParseLines<'_, R> {
+        ParseLines {
+            parser: self,
+            reader,
+            buffer: Vec::new(),
+            line: 0,
+            column: None,
+        }
+    }
+}
+
+/// An iterator over the durations of inputs, see [`Parser::parse_many`](struct.Parser.html#method.parse_many).
+#[derive(Debug)]
+pub struct ParseMany<'a, I> {
+    parser: &'a mut Parser,
+    inputs: I,
+}
+
+impl<I> Iterator for ParseMany<'_, I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = Result<Duration, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let input = self.inputs.next()?;
+        Some(self.parser.parse(input.as_ref()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inputs.size_hint()
+    }
+}
+
+/// An iterator over the durations of the lines of a reader, see
+/// [`Parser::parse_lines`](struct.Parser.html#method.parse_lines).
+#[derive(Debug)]
+pub struct ParseLines<'a, R> {
+    parser: &'a mut Parser,
+    reader: R,
+    buffer: Vec<u8>,
+    line: usize,
+    column: Option<(char, usize)>,
+}
+
+impl<R> ParseLines<'_, R> {
+    /// Only parse the column at `index`, counting from 0, of each line, where columns are
+    /// separated by `delimiter`, like `','` in a CSV file.
+    ///
+    /// A column may be in double quotes, which may contain the delimiter, like `"1h, 30m"`.
+    /// The quotes and whitespace around the column are not part of the input.
+    /// A line without the column is an [`Error::NoValueFound`](../parse/enum.Error.html#variant.NoValueFound).
+    ///
+    /// ```
+    /// use ::parse_duration0::Parser;
+    /// use ::std::time::Duration;
+    ///
+    /// let csv = "job,duration\nbuild,\"1h, 30m\"\ntest,45s\n";
+    /// let mut parser = Parser::default();
+    /// let mut lines = parser.parse_lines(csv.as_bytes()).column(',', 1).skip(1);
+    /// assert_eq!(lines.next().unwrap()?, (2, Ok(Duration::new(5_400, 0))));
+    /// assert_eq!(lines.next().unwrap()?, (3, Ok(Duration::new(45, 0))));
+    /// assert!(lines.next().is_none());
+    /// # Ok::<(), ::std::io::Error>(())
+    /// ```
+    pub fn column(mut self, delimiter: char, index: usize) -> Self {
+        self.column = Some((delimiter, index));
+        self
+    }
+}
+
+/// The column at `index` of `line`, without the quotes and whitespace around it.
+fn column(line: &str, delimiter: char, index: usize) -> Option<&str> {
+    let mut start = 0;
+    let mut quoted = false;
+    let mut columns = 0;
+    for (position, c) in line.char_indices() {
+        if c == '"' {
+            quoted = !quoted;
+        } else if c == delimiter && !quoted {
+            if columns == index {
+                return Some(unquote(&line[start..position]));
+            }
+            columns += 1;
+            start = position + c.len_utf8();
+        }
+    }
+    if columns == index {
+        Some(unquote(&line[start..]))
+    } else {
+        None
+    }
+}
+
+fn unquote(column: &str) -> &str {
+    let column = column.trim();
+    column
+        .strip_prefix('"')
+        .and_then(|column| column.strip_suffix('"'))
+        .unwrap_or(column)
+}
+
+impl<R: BufRead> Iterator for ParseLines<'_, R> {
+    type Item = io::Result<(usize, Result<Duration, Error>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buffer.clear();
+        match self.reader.read_until(b'\n', &mut self.buffer) {
+            Ok(0) => return None,
+            Ok(_) => {}
+            Err(error) => return Some(Err(error)),
+        }
+        self.line += 1;
+        let line = match ::std::str::from_utf8(&self.buffer) {
+            Ok(line) => line,
+            Err(error) => return Some(Err(io::Error::new(io::ErrorKind::InvalidData, error))),
+        };
+        let line = line.strip_suffix('\n').unwrap_or(line);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        let result = match self.column {
+            None => self.parser.parse(line),
+            Some((delimiter, index)) => match column(line, delimiter, index) {
+                Some(column) => self.parser.parse(column),
+                None => Err(Error::NoValueFound(line.to_owned())),
+            },
+        };
+        Some(Ok((self.line, result)))
+    }
+}
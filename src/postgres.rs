@@ -0,0 +1,689 @@
+//! The text format of the `interval` type of PostgreSQL, in each of its `IntervalStyle`s.
+//!
+//! See [the PostgreSQL documentation](https://www.postgresql.org/docs/current/datatype-datetime.html#DATATYPE-INTERVAL-INPUT).
+
+use ::std::time::Duration;
+
+use crate::parse::{duration_from_nanoseconds, Error};
+
+const MICROSECONDS_PER_SECOND: i64 = 1_000_000;
+const MICROSECONDS_PER_MINUTE: i64 = 60 * MICROSECONDS_PER_SECOND;
+const MICROSECONDS_PER_HOUR: i64 = 60 * MICROSECONDS_PER_MINUTE;
+const MICROSECONDS_PER_DAY: i64 = 24 * MICROSECONDS_PER_HOUR;
+
+/// The number of decimals that are used. More cannot change the microseconds of a value.
+const MAX_FRACTION_DIGITS: usize = 18;
+
+/// An `interval` as PostgreSQL stores it: months, days and microseconds, each with their own sign.
+///
+/// Months and days are kept apart from the microseconds, since their length depends on the date
+/// they are added to.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Hash)]
+pub struct PgInterval {
+    pub months: i32,
+    pub days: i32,
+    pub microseconds: i64,
+}
+
+impl PgInterval {
+    /// An interval of only microseconds, rounded down from `duration`.
+    ///
+    /// This fails with [`Error::Overflow`](../parse/enum.Error.html#variant.Overflow)
+    /// if there are more than `i64::MAX` microseconds.
+    pub fn from_duration(duration: Duration) -> Result<Self, Error> {
+        Ok(PgInterval {
+            months: 0,
+            days: 0,
+            microseconds: duration
+                .as_micros()
+                .try_into()
+                .map_err(|_| Error::Overflow)?,
+        })
+    }
+
+    /// The length of the interval as PostgreSQL's `extract(epoch from interval)` computes it:
+    /// a year is 365.25 days, a month is 30 days, and a day is 24 hours.
+    ///
+    /// Negative intervals are an [`Error::OutOfBounds`](../parse/enum.Error.html#variant.OutOfBounds),
+    /// since `Duration`s are positive.
+    ///
+    /// ```
+    /// use ::parse_duration0::{parse_postgres, IntervalStyle};
+    /// use ::std::time::Duration;
+    ///
+    /// let interval = parse_postgres("1 mon 2 days 03:00:00", IntervalStyle::Postgres).unwrap();
+    /// assert_eq!(interval.to_duration(), Ok(Duration::new(2_775_600, 0)));
+    /// ```
+    pub fn to_duration(&self) -> Result<Duration, Error> {
+        let months = i128::from(self.months);
+        let microseconds = months / 12 * 31_557_600_000_000
+            + months % 12 * 30 * i128::from(MICROSECONDS_PER_DAY)
+            + i128::from(self.days) * i128::from(MICROSECONDS_PER_DAY)
+            + i128::from(self.microseconds);
+        duration_from_nanoseconds(microseconds * 1_000)
+    }
+}
+
+/// The styles in which PostgreSQL writes intervals, as set by `SET IntervalStyle`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum IntervalStyle {
+    /// `1 year 2 mons 3 days 04:05:06`, the default.
+    Postgres,
+    /// `@ 1 year 2 mons 3 days 4 hours 5 mins 6 secs`, with `ago` for negative intervals.
+    PostgresVerbose,
+    /// `1-2 3 4:05:06`, the format of the SQL standard.
+    SqlStandard,
+    /// `P1Y2M3DT4H5M6S`, the format with designators of ISO 8601.
+    Iso8601,
+}
+
+/// A number like `"-1.5"`, which is `numerator / scale`.
+#[derive(Debug, Clone, Copy)]
+struct Number {
+    numerator: i128,
+    scale: i128,
+}
+
+impl Number {
+    /// Read a number with an optional sign and decimals, or `None` if `text` is not a number.
+    fn parse(text: &str) -> Option<Result<Number, Error>> {
+        let digits = text.strip_prefix(['+', '-']).unwrap_or(text);
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if whole.len() + fraction.len() == 0 || !is_digits(whole) || !is_digits(fraction) {
+            return None;
+        }
+        Some(Number::from_digits(whole, fraction, text.starts_with('-')))
+    }
+
+    fn from_digits(whole: &str, fraction: &str, negative: bool) -> Result<Number, Error> {
+        let fraction = &fraction[..fraction.len().min(MAX_FRACTION_DIGITS)];
+        let scale = 10_i128.pow(fraction.len() as u32);
+        let value = |part: &str| match part {
+            "" => Ok(0),
+            part => part.parse::<i64>().map_err(|_| Error::Overflow),
+        };
+        let numerator = i128::from(value(whole)?) * scale + i128::from(value(fraction)?);
+        Ok(Number {
+            numerator: if negative { -numerator } else { numerator },
+            scale,
+        })
+    }
+}
+
+/// `numerator / denominator`, rounded to the nearest integer and to even on ties, like `rint`.
+fn round(numerator: i128, denominator: i128) -> i128 {
+    let quotient = numerator / denominator;
+    let twice_remainder = 2 * (numerator % denominator).abs();
+    if twice_remainder > denominator || twice_remainder == denominator && quotient % 2 != 0 {
+        quotient + numerator.signum()
+    } else {
+        quotient
+    }
+}
+
+/// What a unit adds to an interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    /// Years, decades and so on, with this many months. Decimals are rounded to whole months.
+    Years(i128),
+    /// Months, where decimals are 30 days.
+    Months,
+    /// Weeks, where decimals are 7 days.
+    Weeks,
+    /// Days, where decimals are 24 hours.
+    Days,
+    /// Units of time with this many microseconds.
+    Microseconds(i64),
+}
+
+/// The field of a unit word, in lower case, as PostgreSQL accepts them.
+fn field(word: &str) -> Option<Field> {
+    Some(match word {
+        "millennium" | "millennia" | "millenniums" | "mil" | "mils" => Field::Years(12_000),
+        "century" | "centuries" | "cent" | "c" => Field::Years(1_200),
+        "decade" | "decades" | "dec" | "decs" => Field::Years(120),
+        "year" | "years" | "yr" | "yrs" | "y" => Field::Years(12),
+        "month" | "months" | "mon" | "mons" => Field::Months,
+        "week" | "weeks" | "w" => Field::Weeks,
+        "day" | "days" | "d" => Field::Days,
+        "hour" | "hours" | "hr" | "hrs" | "h" => Field::Microseconds(MICROSECONDS_PER_HOUR),
+        "minute" | "minutes" | "min" | "mins" | "m" => Field::Microseconds(MICROSECONDS_PER_MINUTE),
+        "second" | "seconds" | "sec" | "secs" | "s" => Field::Microseconds(MICROSECONDS_PER_SECOND),
+        "millisecond" | "milliseconds" | "msec" | "msecs" | "ms" => Field::Microseconds(1_000),
+        "microsecond" | "microseconds" | "usec" | "usecs" | "us" => Field::Microseconds(1),
+        _ => return None,
+    })
+}
+
+/// An interval while it is being summed, with fields that may be out of range.
+#[derive(Debug, Clone, Copy, Default)]
+struct Sum {
+    months: i128,
+    days: i128,
+    microseconds: i128,
+}
+
+impl Sum {
+    fn add(&mut self, other: Sum) -> Result<(), Error> {
+        let add = |a: i128, b: i128| a.checked_add(b).ok_or(Error::Overflow);
+        self.months = add(self.months, other.months)?;
+        self.days = add(self.days, other.days)?;
+        self.microseconds = add(self.microseconds, other.microseconds)?;
+        Ok(())
+    }
+
+    fn negate(&mut self) {
+        self.months = -self.months;
+        self.days = -self.days;
+        self.microseconds = -self.microseconds;
+    }
+
+    /// The interval of `number` times `field`. Decimals are spread over the smaller fields
+    /// the way PostgreSQL does, so "1.5 mons" is 1 month and 15 days.
+    fn of(number: Number, field: Field) -> Result<Sum, Error> {
+        let Number { numerator, scale } = number;
+        let mul = |a: i128, b: i128| a.checked_mul(b).ok_or(Error::Overflow);
+        // The rest of `numerator / scale` in days, spread over the microseconds.
+        let days = |numerator: i128| -> Result<Sum, Error> {
+            Ok(Sum {
+                months: 0,
+                days: numerator / scale,
+                microseconds: round(mul(numerator % scale, MICROSECONDS_PER_DAY.into())?, scale),
+            })
+        };
+        match field {
+            Field::Years(months) => Ok(Sum {
+                months: round(mul(numerator, months)?, scale),
+                ..Sum::default()
+            }),
+            Field::Months => {
+                let mut sum = days(mul(numerator % scale, 30)?)?;
+                sum.months = numerator / scale;
+                Ok(sum)
+            }
+            Field::Weeks => days(mul(numerator, 7)?),
+            Field::Days => days(numerator),
+            Field::Microseconds(microseconds) => Ok(Sum {
+                microseconds: round(mul(numerator, microseconds.into())?, scale),
+                ..Sum::default()
+            }),
+        }
+    }
+
+    fn into_interval(self) -> Result<PgInterval, Error> {
+        Ok(PgInterval {
+            months: self.months.try_into().map_err(|_| Error::Overflow)?,
+            days: self.days.try_into().map_err(|_| Error::Overflow)?,
+            microseconds: self.microseconds.try_into().map_err(|_| Error::Overflow)?,
+        })
+    }
+}
+
+/// Read a time like `"-4:05:06.789"` or `"4:05"` as microseconds, or `None` if it is not a time.
+fn clock(text: &str) -> Option<Result<Sum, Error>> {
+    let digits = text.strip_prefix(['+', '-']).unwrap_or(text);
+    let mut parts = digits.split(':');
+    let (first, second, third) = (parts.next()?, parts.next()?, parts.next());
+    if parts.next().is_some() {
+        return None;
+    }
+    let whole = |part: &str| -> Option<i64> {
+        (!part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+            .then(|| part.parse().ok())
+            .flatten()
+    };
+    // "4:05" is hours and minutes, but "4:05.5" is minutes and seconds.
+    let (hours, minutes, seconds) = match third {
+        Some(seconds) => (whole(first)?, whole(second)?, seconds),
+        None if second.contains('.') => (0, whole(first)?, second),
+        None => (whole(first)?, whole(second)?, "0"),
+    };
+    if seconds.starts_with(['+', '-']) {
+        return None;
+    }
+    let seconds = match Number::parse(seconds)? {
+        Ok(seconds) => seconds,
+        Err(error) => return Some(Err(error)),
+    };
+    if minutes >= 60 || seconds.numerator >= 60 * seconds.scale {
+        return Some(Err(Error::InvalidFormat(text.to_owned())));
+    }
+    let time = i128::from(hours) * i128::from(MICROSECONDS_PER_HOUR)
+        + i128::from(minutes) * i128::from(MICROSECONDS_PER_MINUTE)
+        + round(
+            seconds.numerator * i128::from(MICROSECONDS_PER_SECOND),
+            seconds.scale,
+        );
+    let microseconds = if text.starts_with('-') { -time } else { time };
+    Some(Ok(Sum {
+        microseconds,
+        ..Sum::default()
+    }))
+}
+
+/// Read a year and month like `"1-2"` or `"-1-2"` as months, or `None` if it is not one.
+fn year_month(text: &str) -> Option<Result<Sum, Error>> {
+    let digits = text.strip_prefix(['+', '-']).unwrap_or(text);
+    let (years, months) = digits.split_once('-')?;
+    let is_digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    if !is_digits(years) || !is_digits(months) {
+        return None;
+    }
+    // Years are parsed as `i64`, so they can not overflow when multiplied.
+    let (Ok(years), Ok(months)) = (years.parse::<i64>(), months.parse::<i128>()) else {
+        return Some(Err(Error::Overflow));
+    };
+    if months >= 12 {
+        return Some(Err(Error::InvalidFormat(text.to_owned())));
+    }
+    let total = i128::from(years) * 12 + months;
+    Some(Ok(Sum {
+        months: if text.starts_with('-') { -total } else { total },
+        ..Sum::default()
+    }))
+}
+
+/// Parse the ISO 8601 format with designators, like `"P1Y2M3DT4H5M6S"`.
+fn parse_iso8601(input: &str) -> Result<Sum, Error> {
+    let invalid = || Error::InvalidFormat(input.to_owned());
+    let mut rest = input.strip_prefix('P').ok_or_else(invalid)?;
+    let mut sum = Sum::default();
+    let mut in_time = false;
+    let mut found = false;
+    while !rest.is_empty() {
+        if let Some(time) = rest.strip_prefix('T').filter(|_| !in_time) {
+            in_time = true;
+            rest = time;
+            continue;
+        }
+        let end = rest
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '+' | '-' | '.')))
+            .ok_or_else(invalid)?;
+        let number = Number::parse(&rest[..end]).ok_or_else(invalid)??;
+        let field = match (in_time, rest.as_bytes()[end]) {
+            (false, b'Y') => Field::Years(12),
+            (false, b'M') => Field::Months,
+            (false, b'W') => Field::Weeks,
+            (false, b'D') => Field::Days,
+            (true, b'H') => Field::Microseconds(MICROSECONDS_PER_HOUR),
+            (true, b'M') => Field::Microseconds(MICROSECONDS_PER_MINUTE),
+            (true, b'S') => Field::Microseconds(MICROSECONDS_PER_SECOND),
+            _ => return Err(invalid()),
+        };
+        sum.add(Sum::of(number, field)?)?;
+        found = true;
+        rest = &rest[end + 1..];
+    }
+    if found && !input.ends_with('T') {
+        Ok(sum)
+    } else {
+        Err(invalid())
+    }
+}
+
+/// Split a word like `"3days"` into its number and unit.
+fn split_unit(word: &str) -> (&str, &str) {
+    match word.find(|c: char| c.is_alphabetic()) {
+        Some(0) | None => (word, ""),
+        Some(index) => word.split_at(index),
+    }
+}
+
+/// Parse an interval in any of the output styles of PostgreSQL, or in its other input syntax,
+/// like `"1 year 2 mons 3 days 04:05:06.789"`, `"@ 1 hour ago"` or `"P1Y2M3DT4H5M6S"`.
+///
+/// `style` is the `IntervalStyle` of the database. It only matters for inputs like `"-1 2:03:04"`,
+/// where the sign applies to all fields with the SQL standard style, as in PostgreSQL.
+///
+/// The input is a sequence of values with a unit, like `"3 days"` or `"3days"`, times like
+/// `"04:05:06"`, and years and months like `"1-2"`. It may start with `@` and end with `ago`,
+/// which negates it. A value without a unit is a number of days if a time follows it, and seconds
+/// otherwise. Units are not case-sensitive, and the ones of PostgreSQL are accepted, from
+/// `microseconds` up to `millennia`, with abbreviations like `mons`, `hrs` and `secs`.
+///
+/// Decimals are spread over smaller fields like in PostgreSQL, so `"1.5 years"` is 1 year and 6
+/// months, `"1.5 mons"` is 1 month and 15 days, and `"1.5 days"` is 1 day and 12 hours.
+///
+/// The "alternative format" of ISO 8601, like `"P0001-02-03T04:05:06"`, is not supported, since
+/// PostgreSQL never writes it.
+///
+/// Fields outside the range of a [`PgInterval`](struct.PgInterval.html) are an
+/// [`Error::Overflow`](../parse/enum.Error.html#variant.Overflow), and other invalid inputs are an
+/// [`Error::InvalidFormat`](../parse/enum.Error.html#variant.InvalidFormat).
+///
+/// ```
+/// use ::parse_duration0::{parse_postgres, IntervalStyle, PgInterval};
+///
+/// let interval = PgInterval { months: 14, days: 3, microseconds: 14_706_789_000 };
+/// let style = IntervalStyle::Postgres;
+/// assert_eq!(parse_postgres("1 year 2 mons 3 days 04:05:06.789", style), Ok(interval));
+/// assert_eq!(parse_postgres("P1Y2M3DT4H5M6.789S", style), Ok(interval));
+/// assert_eq!(parse_postgres("1-2 3 4:05:06.789", style), Ok(interval));
+/// assert_eq!(
+///     parse_postgres("@ 1 hour ago", style),
+///     Ok(PgInterval { months: 0, days: 0, microseconds: -3_600_000_000 })
+/// );
+/// ```
+pub fn parse_postgres(input: &str, style: IntervalStyle) -> Result<PgInterval, Error> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(Error::NoValueFound(String::new()));
+    }
+    if input.starts_with('P') {
+        return parse_iso8601(input)?.into_interval();
+    }
+    let invalid = || Error::InvalidFormat(input.to_owned());
+    let lower = input.to_lowercase();
+    let text = lower.strip_prefix('@').unwrap_or(&lower);
+    let mut words: Vec<&str> = text.split_whitespace().collect();
+    let ago = words.last() == Some(&"ago");
+    if ago {
+        words.pop();
+    }
+    // The value of each field, and whether it has an explicit sign.
+    let mut fields: Vec<(Sum, bool)> = Vec::new();
+    let mut index = 0;
+    while index < words.len() {
+        let word = words[index];
+        index += 1;
+        let signed = word.starts_with(['+', '-']);
+        if let Some(sum) = clock(word).or_else(|| year_month(word)) {
+            fields.push((sum?, signed));
+            continue;
+        }
+        let (number, unit) = split_unit(word);
+        let number = Number::parse(number).ok_or_else(invalid)??;
+        // The unit may also be the next word, like in "3 days".
+        let unit = match (unit, words.get(index)) {
+            ("", Some(next)) if next.starts_with(char::is_alphabetic) => {
+                index += 1;
+                *next
+            }
+            (unit, _) => unit,
+        };
+        let field = match unit {
+            "" if words.get(index).is_some_and(|&next| clock(next).is_some()) => Field::Days,
+            "" => Field::Microseconds(MICROSECONDS_PER_SECOND),
+            unit => field(unit).ok_or_else(|| Error::UnknownUnit(unit.to_owned()))?,
+        };
+        fields.push((Sum::of(number, field)?, signed));
+    }
+    if fields.is_empty() {
+        return Err(invalid());
+    }
+    // With the SQL standard style, a sign in front applies to all fields without their own sign.
+    let negate_rest = style == IntervalStyle::SqlStandard
+        && words[0].starts_with('-')
+        && fields[1..].iter().all(|&(_, signed)| !signed);
+    let mut sum = Sum::default();
+    for (index, (mut field, _)) in fields.into_iter().enumerate() {
+        if negate_rest && index > 0 {
+            field.negate();
+        }
+        sum.add(field)?;
+    }
+    if ago {
+        sum.negate();
+    }
+    sum.into_interval()
+}
+
+/// The fields in which PostgreSQL writes an interval. All have the sign of their source field.
+struct Parts {
+    years: i64,
+    months: i64,
+    days: i64,
+    hours: i64,
+    minutes: i64,
+    seconds: i64,
+    microseconds: i64,
+}
+
+impl Parts {
+    fn new(interval: &PgInterval) -> Self {
+        let months = i64::from(interval.months);
+        let time = interval.microseconds;
+        Parts {
+            years: months / 12,
+            months: months % 12,
+            days: interval.days.into(),
+            hours: time / MICROSECONDS_PER_HOUR,
+            minutes: time / MICROSECONDS_PER_MINUTE % 60,
+            seconds: time / MICROSECONDS_PER_SECOND % 60,
+            microseconds: time % MICROSECONDS_PER_SECOND,
+        }
+    }
+
+    fn has_time(&self) -> bool {
+        self.hours != 0 || self.minutes != 0 || self.seconds != 0 || self.microseconds != 0
+    }
+
+    fn negative_time(&self) -> bool {
+        self.hours < 0 || self.minutes < 0 || self.seconds < 0 || self.microseconds < 0
+    }
+
+    /// The seconds without a sign, with two digits if `pad`, and with as many decimals as needed.
+    fn seconds(&self, pad: bool) -> String {
+        let seconds = self.seconds.unsigned_abs();
+        let seconds = if pad {
+            format!("{:02}", seconds)
+        } else {
+            seconds.to_string()
+        };
+        match self.microseconds.unsigned_abs() {
+            0 => seconds,
+            fraction => {
+                let fraction = format!("{:06}", fraction);
+                format!("{}.{}", seconds, fraction.trim_end_matches('0'))
+            }
+        }
+    }
+
+    /// The time without a sign, like `"04:05:06.789"` if `pad` or else `"4:05:06.789"`.
+    fn clock(&self, pad: bool) -> String {
+        let hours = self.hours.unsigned_abs();
+        let hours = if pad {
+            format!("{:02}", hours)
+        } else {
+            hours.to_string()
+        };
+        format!(
+            "{}:{:02}:{}",
+            hours,
+            self.minutes.unsigned_abs(),
+            self.seconds(true)
+        )
+    }
+}
+
+fn format_default(parts: &Parts) -> String {
+    let mut text = String::new();
+    let mut before = false;
+    for (value, unit) in [
+        (parts.years, "year"),
+        (parts.months, "mon"),
+        (parts.days, "day"),
+    ] {
+        if value == 0 {
+            continue;
+        }
+        if !text.is_empty() {
+            text.push(' ');
+        }
+        let sign = if before && value > 0 { "+" } else { "" };
+        let plural = if value == 1 { "" } else { "s" };
+        text.push_str(&format!("{}{} {}{}", sign, value, unit, plural));
+        before = value < 0;
+    }
+    if text.is_empty() || parts.has_time() {
+        if !text.is_empty() {
+            text.push(' ');
+        }
+        if parts.negative_time() {
+            text.push('-');
+        } else if before {
+            text.push('+');
+        }
+        text.push_str(&parts.clock(true));
+    }
+    text
+}
+
+fn format_verbose(parts: &Parts) -> String {
+    let mut text = String::from("@");
+    let mut zero = true;
+    let mut before = false;
+    for (value, unit) in [
+        (parts.years, "year"),
+        (parts.months, "mon"),
+        (parts.days, "day"),
+        (parts.hours, "hour"),
+        (parts.minutes, "min"),
+    ] {
+        if value == 0 {
+            continue;
+        }
+        // The first field decides whether the interval is "ago", and the others are relative to it.
+        let value = if zero {
+            before = value < 0;
+            value.abs()
+        } else if before {
+            -value
+        } else {
+            value
+        };
+        let plural = if value == 1 { "" } else { "s" };
+        text.push_str(&format!(" {} {}{}", value, unit, plural));
+        zero = false;
+    }
+    if parts.seconds != 0 || parts.microseconds != 0 {
+        text.push(' ');
+        let negative = parts.seconds < 0 || parts.microseconds < 0;
+        if zero {
+            before = negative;
+        } else if negative != before {
+            text.push('-');
+        }
+        text.push_str(&parts.seconds(false));
+        let plural = if parts.seconds.abs() == 1 && parts.microseconds == 0 {
+            ""
+        } else {
+            "s"
+        };
+        text.push_str(&format!(" sec{}", plural));
+        zero = false;
+    }
+    if zero {
+        text.push_str(" 0");
+    }
+    if before {
+        text.push_str(" ago");
+    }
+    text
+}
+
+fn format_sql_standard(parts: &Parts) -> String {
+    let values = [
+        parts.years,
+        parts.months,
+        parts.days,
+        parts.hours,
+        parts.minutes,
+        parts.seconds,
+        parts.microseconds,
+    ];
+    let negative = values.iter().any(|&value| value < 0);
+    let positive = values.iter().any(|&value| value > 0);
+    let year_month = parts.years != 0 || parts.months != 0;
+    let day_time = parts.days != 0 || parts.has_time();
+    if !negative && !positive {
+        return "0".to_owned();
+    }
+    if negative && positive || year_month && day_time {
+        // Not a value of the SQL standard, so every field gets a sign.
+        let sign = |negative: bool| if negative { '-' } else { '+' };
+        return format!(
+            "{}{}-{} {}{} {}{}",
+            sign(parts.years < 0 || parts.months < 0),
+            parts.years.unsigned_abs(),
+            parts.months.unsigned_abs(),
+            sign(parts.days < 0),
+            parts.days.unsigned_abs(),
+            sign(parts.negative_time()),
+            parts.clock(false)
+        );
+    }
+    let sign = if negative { "-" } else { "" };
+    if year_month {
+        format!(
+            "{}{}-{}",
+            sign,
+            parts.years.unsigned_abs(),
+            parts.months.unsigned_abs()
+        )
+    } else if parts.days != 0 {
+        format!(
+            "{}{} {}",
+            sign,
+            parts.days.unsigned_abs(),
+            parts.clock(false)
+        )
+    } else {
+        format!("{}{}", sign, parts.clock(false))
+    }
+}
+
+fn format_iso8601(parts: &Parts) -> String {
+    if parts.years == 0 && parts.months == 0 && parts.days == 0 && !parts.has_time() {
+        return "PT0S".to_owned();
+    }
+    let mut text = String::from("P");
+    let designated = |text: &mut String, value: i64, designator: char| {
+        if value != 0 {
+            text.push_str(&format!("{}{}", value, designator));
+        }
+    };
+    designated(&mut text, parts.years, 'Y');
+    designated(&mut text, parts.months, 'M');
+    designated(&mut text, parts.days, 'D');
+    if parts.has_time() {
+        text.push('T');
+    }
+    designated(&mut text, parts.hours, 'H');
+    designated(&mut text, parts.minutes, 'M');
+    if parts.seconds != 0 || parts.microseconds != 0 {
+        if parts.seconds < 0 || parts.microseconds < 0 {
+            text.push('-');
+        }
+        text.push_str(&parts.seconds(false));
+        text.push('S');
+    }
+    text
+}
+
+/// Format an interval the way PostgreSQL does with the `IntervalStyle` `style`.
+///
+/// The result can be read back by [`parse_postgres`](fn.parse_postgres.html) with the same style.
+///
+/// ```
+/// use ::parse_duration0::{format_postgres, IntervalStyle, PgInterval};
+///
+/// let interval = PgInterval { months: 14, days: 3, microseconds: 14_706_000_000 };
+/// assert_eq!(format_postgres(&interval, IntervalStyle::Postgres), "1 year 2 mons 3 days 04:05:06");
+/// assert_eq!(
+///     format_postgres(&interval, IntervalStyle::PostgresVerbose),
+///     "@ 1 year 2 mons 3 days 4 hours 5 mins 6 secs"
+/// );
+/// assert_eq!(format_postgres(&interval, IntervalStyle::SqlStandard), "+1-2 +3 +4:05:06");
+/// assert_eq!(format_postgres(&interval, IntervalStyle::Iso8601), "P1Y2M3DT4H5M6S");
+/// ```
+pub fn format_postgres(interval: &PgInterval, style: IntervalStyle) -> String {
+    let parts = Parts::new(interval);
+    match style {
+        IntervalStyle::Postgres => format_default(&parts),
+        IntervalStyle::PostgresVerbose => format_verbose(&parts),
+        IntervalStyle::SqlStandard => format_sql_standard(&parts),
+        IntervalStyle::Iso8601 => format_iso8601(&parts),
+    }
+}
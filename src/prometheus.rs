@@ -0,0 +1,141 @@
+//! The duration syntax of Prometheus, as in scrape intervals and range selectors like `[5m]`.
+//!
+//! This follows `model.ParseDuration` and `model.Duration.String` of
+//! [prometheus/common](https://github.com/prometheus/common/blob/main/model/time.go).
+
+use ::std::time::Duration;
+
+use crate::parse::Error;
+
+const MILLISECOND: u64 = 1_000_000;
+const SECOND: u64 = 1_000 * MILLISECOND;
+const MINUTE: u64 = 60 * SECOND;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+const YEAR: u64 = 365 * DAY;
+
+/// The units in order from largest to smallest, with their length in nanoseconds.
+const UNITS: [(&str, u64); 7] = [
+    ("y", YEAR),
+    ("w", WEEK),
+    ("d", DAY),
+    ("h", HOUR),
+    ("m", MINUTE),
+    ("s", SECOND),
+    ("ms", MILLISECOND),
+];
+
+/// Parse a duration like `"1h30m"` the way Prometheus does.
+///
+/// Compared to [`parse`](../parse/fn.parse.html), this is much stricter:
+///
+/// * The units are `y` (always 365 days), `w`, `d`, `h`, `m`, `s` and `ms`.
+/// * Values are whole numbers without a sign, and each needs a unit. Only `"0"` may be left without one.
+/// * Units go from largest to smallest and each unit occurs once, so `"30m1h"` is invalid.
+/// * There can be no spaces or other characters.
+/// * The duration must be less than 2<sup>63</sup> nanoseconds, about 292 years.
+///
+/// Invalid inputs give the same errors as in Prometheus: an [`Error::UnknownUnit`] for
+/// `"unknown unit"`, an [`Error::Overflow`] for `"duration out of range"`, an
+/// [`Error::NoValueFound`] for an empty input, and an [`Error::InvalidFormat`] for the rest.
+///
+/// [`Error::UnknownUnit`]: ../parse/enum.Error.html#variant.UnknownUnit
+/// [`Error::Overflow`]: ../parse/enum.Error.html#variant.Overflow
+/// [`Error::NoValueFound`]: ../parse/enum.Error.html#variant.NoValueFound
+/// [`Error::InvalidFormat`]: ../parse/enum.Error.html#variant.InvalidFormat
+///
+/// ```
+/// use ::parse_duration0::{parse_prometheus, Error};
+/// use ::std::time::Duration;
+///
+/// assert_eq!(parse_prometheus("1h30m"), Ok(Duration::new(5_400, 0)));
+/// assert_eq!(parse_prometheus("1y"), Ok(Duration::new(31_536_000, 0)));
+/// assert_eq!(parse_prometheus("30m1h"), Err(Error::InvalidFormat("30m1h".to_string())));
+/// ```
+pub fn parse_prometheus(input: &str) -> Result<Duration, Error> {
+    match input {
+        "0" => return Ok(Duration::new(0, 0)),
+        "" => return Err(Error::NoValueFound(String::new())),
+        _ => {}
+    }
+    let invalid = || Error::InvalidFormat(input.to_owned());
+    let bytes = input.as_bytes();
+    let mut position = 0;
+    let mut total: u64 = 0;
+    // The index in `UNITS` after the last unit, since units must go from largest to smallest.
+    let mut next_unit = 0;
+    while position < bytes.len() {
+        let digits = bytes[position..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        if digits == 0 {
+            return Err(invalid());
+        }
+        let value: u64 = input[position..position + digits]
+            .parse()
+            .map_err(|_| invalid())?;
+        position += digits;
+        let letters = bytes[position..]
+            .iter()
+            .take_while(|b| !b.is_ascii_digit())
+            .count();
+        if letters == 0 {
+            return Err(invalid());
+        }
+        let unit = &input[position..position + letters];
+        position += letters;
+        let index = UNITS
+            .iter()
+            .position(|&(name, _)| name == unit)
+            .ok_or_else(|| Error::UnknownUnit(unit.to_owned()))?;
+        if index < next_unit {
+            return Err(invalid());
+        }
+        next_unit = index + 1;
+        let nanoseconds = UNITS[index].1;
+        if value > (1 << 63) / nanoseconds {
+            return Err(Error::Overflow);
+        }
+        total += value * nanoseconds;
+        if total > i64::MAX as u64 {
+            return Err(Error::Overflow);
+        }
+    }
+    Ok(Duration::from_nanos(total))
+}
+
+/// Format a duration the way Prometheus does, such as `"1h30m"` or `"90d"`.
+///
+/// Years and weeks are only used if they divide the duration exactly, and the duration is
+/// rounded down to whole milliseconds. A duration of less than a millisecond is `"0s"`.
+///
+/// ```
+/// use ::parse_duration0::format_prometheus;
+/// use ::std::time::Duration;
+///
+/// assert_eq!(format_prometheus(Duration::new(5_400, 0)), "1h30m");
+/// assert_eq!(format_prometheus(Duration::new(90 * 86_400, 0)), "90d");
+/// assert_eq!(format_prometheus(Duration::new(0, 1_500_000)), "1ms");
+/// ```
+pub fn format_prometheus(duration: Duration) -> String {
+    let mut milliseconds = duration.as_millis();
+    if milliseconds == 0 {
+        return "0s".to_owned();
+    }
+    let mut text = String::new();
+    for (index, &(name, nanoseconds)) in UNITS.iter().enumerate() {
+        let unit = u128::from(nanoseconds / MILLISECOND);
+        // Only years and weeks need to be exact, as it is easier to read "90d" than "12w6d".
+        if index < 2 && milliseconds % unit != 0 {
+            continue;
+        }
+        let value = milliseconds / unit;
+        if value > 0 {
+            text.push_str(&format!("{}{}", value, name));
+            milliseconds -= value * unit;
+        }
+    }
+    text
+}
@@ -0,0 +1,137 @@
+//! Python bindings, built with PyO3 and maturin: `maturin develop --features python`.
+//!
+//! The module is called `parse_duration`. It has `parse`, which returns a `datetime.timedelta`,
+//! and `format`. Errors are raised as subclasses of `ParseDurationError`, which is a `ValueError`:
+//! `UnknownUnitError` for [`Error::UnknownUnit`](../parse/enum.Error.html#variant.UnknownUnit),
+//! and so on. [`Error::Overflow`](../parse/enum.Error.html#variant.Overflow) is a
+//! `DurationOverflowError`, so that it does not shadow the builtin `OverflowError`.
+
+use ::std::time::Duration;
+
+use ::pyo3::create_exception;
+use ::pyo3::exceptions::PyValueError;
+use ::pyo3::prelude::*;
+
+use crate::parse::Error;
+
+/// The number of days of `datetime.timedelta.max`.
+const MAX_TIMEDELTA_DAYS: u64 = 999_999_999;
+
+create_exception!(parse_duration, ParseDurationError, PyValueError);
+create_exception!(parse_duration, ParseIntError, ParseDurationError);
+create_exception!(parse_duration, UnknownUnitError, ParseDurationError);
+create_exception!(parse_duration, OutOfBoundsError, ParseDurationError);
+create_exception!(parse_duration, DurationOverflowError, ParseDurationError);
+create_exception!(parse_duration, ExpNotSupportedError, ParseDurationError);
+create_exception!(parse_duration, NoUnitFoundError, ParseDurationError);
+create_exception!(parse_duration, NoValueFoundError, ParseDurationError);
+create_exception!(parse_duration, DivisionByZeroError, ParseDurationError);
+create_exception!(parse_duration, UnexpectedTokenError, ParseDurationError);
+create_exception!(parse_duration, UnexpectedEndError, ParseDurationError);
+create_exception!(parse_duration, InvalidOperationError, ParseDurationError);
+create_exception!(parse_duration, NestingTooDeepError, ParseDurationError);
+create_exception!(parse_duration, InvalidRangeError, ParseDurationError);
+create_exception!(parse_duration, TooShortError, ParseDurationError);
+create_exception!(parse_duration, TooLongError, ParseDurationError);
+create_exception!(parse_duration, UnitNotAllowedError, ParseDurationError);
+create_exception!(parse_duration, ZeroNotAllowedError, ParseDurationError);
+create_exception!(parse_duration, InvalidFormatError, ParseDurationError);
+create_exception!(parse_duration, InputTooLongError, ParseDurationError);
+create_exception!(parse_duration, TooManyComponentsError, ParseDurationError);
+create_exception!(parse_duration, AmbiguousUnitError, ParseDurationError);
+
+impl From<Error> for PyErr {
+    fn from(error: Error) -> PyErr {
+        let message = error.to_string();
+        match error {
+            Error::ParseInt(_) => ParseIntError::new_err(message),
+            Error::UnknownUnit(_) => UnknownUnitError::new_err(message),
+            Error::OutOfBounds(_) => OutOfBoundsError::new_err(message),
+            Error::Overflow => DurationOverflowError::new_err(message),
+            Error::ExpNotSupported => ExpNotSupportedError::new_err(message),
+            Error::NoUnitFound(_) => NoUnitFoundError::new_err(message),
+            Error::NoValueFound(_) => NoValueFoundError::new_err(message),
+            Error::DivisionByZero(_) => DivisionByZeroError::new_err(message),
+            Error::UnexpectedToken(_) => UnexpectedTokenError::new_err(message),
+            Error::UnexpectedEnd => UnexpectedEndError::new_err(message),
+            Error::InvalidOperation(_) => InvalidOperationError::new_err(message),
+            Error::NestingTooDeep => NestingTooDeepError::new_err(message),
+            Error::InvalidRange(_) => InvalidRangeError::new_err(message),
+            Error::TooShort { .. } => TooShortError::new_err(message),
+            Error::TooLong { .. } => TooLongError::new_err(message),
+            Error::UnitNotAllowed { .. } => UnitNotAllowedError::new_err(message),
+            Error::ZeroNotAllowed => ZeroNotAllowedError::new_err(message),
+            Error::InvalidFormat(_) => InvalidFormatError::new_err(message),
+            Error::InputTooLong { .. } => InputTooLongError::new_err(message),
+            Error::TooManyComponents { .. } => TooManyComponentsError::new_err(message),
+            Error::AmbiguousUnit { .. } => AmbiguousUnitError::new_err(message),
+        }
+    }
+}
+
+/// Parse a string like "1 hour 15 minutes" into a `datetime.timedelta`.
+///
+/// A `timedelta` has microsecond precision, so nanoseconds are rounded down.
+/// Durations beyond the range of `timedelta`, about 2.7 million years, raise a `DurationOverflowError`.
+#[pyfunction]
+fn parse(input: &str) -> PyResult<Duration> {
+    let duration = crate::parse::parse(input)?;
+    if duration.as_secs() / 86_400 > MAX_TIMEDELTA_DAYS {
+        return Err(Error::Overflow.into());
+    }
+    Ok(duration)
+}
+
+/// Format a `datetime.timedelta` as English text, such as "1 hour 15 minutes and 29 seconds".
+///
+/// Negative durations raise a `ValueError`.
+#[pyfunction]
+fn format(duration: Duration) -> String {
+    crate::format::format(duration)
+}
+
+/// Parse durations like "1 hour 15 minutes", with the same rules as the Rust crate.
+#[pymodule]
+pub fn parse_duration(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = module.py();
+    module.add_function(wrap_pyfunction!(parse, module)?)?;
+    module.add_function(wrap_pyfunction!(format, module)?)?;
+    module.add("ParseDurationError", py.get_type::<ParseDurationError>())?;
+    module.add("ParseIntError", py.get_type::<ParseIntError>())?;
+    module.add("UnknownUnitError", py.get_type::<UnknownUnitError>())?;
+    module.add("OutOfBoundsError", py.get_type::<OutOfBoundsError>())?;
+    module.add(
+        "DurationOverflowError",
+        py.get_type::<DurationOverflowError>(),
+    )?;
+    module.add(
+        "ExpNotSupportedError",
+        py.get_type::<ExpNotSupportedError>(),
+    )?;
+    module.add("NoUnitFoundError", py.get_type::<NoUnitFoundError>())?;
+    module.add("NoValueFoundError", py.get_type::<NoValueFoundError>())?;
+    module.add("DivisionByZeroError", py.get_type::<DivisionByZeroError>())?;
+    module.add(
+        "UnexpectedTokenError",
+        py.get_type::<UnexpectedTokenError>(),
+    )?;
+    module.add("UnexpectedEndError", py.get_type::<UnexpectedEndError>())?;
+    module.add(
+        "InvalidOperationError",
+        py.get_type::<InvalidOperationError>(),
+    )?;
+    module.add("NestingTooDeepError", py.get_type::<NestingTooDeepError>())?;
+    module.add("InvalidRangeError", py.get_type::<InvalidRangeError>())?;
+    module.add("TooShortError", py.get_type::<TooShortError>())?;
+    module.add("TooLongError", py.get_type::<TooLongError>())?;
+    module.add("UnitNotAllowedError", py.get_type::<UnitNotAllowedError>())?;
+    module.add("ZeroNotAllowedError", py.get_type::<ZeroNotAllowedError>())?;
+    module.add("InvalidFormatError", py.get_type::<InvalidFormatError>())?;
+    module.add("InputTooLongError", py.get_type::<InputTooLongError>())?;
+    module.add(
+        "TooManyComponentsError",
+        py.get_type::<TooManyComponentsError>(),
+    )?;
+    module.add("AmbiguousUnitError", py.get_type::<AmbiguousUnitError>())?;
+    Ok(())
+}
@@ -0,0 +1,251 @@
+use ::std::ops::{Bound, RangeBounds};
+use ::std::time::Duration;
+
+use crate::lexer::{Lexer, Token, TokenKind};
+use crate::options::Options;
+use crate::parse::{parse_with_units, Error};
+use crate::words;
+
+/// A range of durations, where each end may be inclusive, exclusive or open.
+///
+/// A range returned by [`parse_range`](fn.parse_range.html) is never empty.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DurationRange {
+    pub min: Bound<Duration>,
+    pub max: Bound<Duration>,
+}
+
+impl DurationRange {
+    /// Whether `duration` is in this range.
+    pub fn contains(&self, duration: &Duration) -> bool {
+        RangeBounds::contains(self, duration)
+    }
+
+    /// Whether no duration at all is in this range.
+    pub fn is_empty(&self) -> bool {
+        match (self.min, self.max) {
+            (Bound::Included(min), Bound::Included(max)) => min > max,
+            (Bound::Included(min), Bound::Excluded(max))
+            | (Bound::Excluded(min), Bound::Included(max))
+            | (Bound::Excluded(min), Bound::Excluded(max)) => min >= max,
+            (_, Bound::Excluded(max)) => max == Duration::new(0, 0),
+            _ => false,
+        }
+    }
+}
+
+impl RangeBounds<Duration> for DurationRange {
+    fn start_bound(&self) -> Bound<&Duration> {
+        self.min.as_ref()
+    }
+
+    fn end_bound(&self) -> Bound<&Duration> {
+        self.max.as_ref()
+    }
+}
+
+/// The tokens of `input`, without whitespace.
+/// With `number_words`, values written as words are single tokens if the options allow them.
+fn significant_tokens(
+    input: &str,
+    options: &Options,
+    number_words: bool,
+) -> Result<Vec<Token>, Error> {
+    let config = options.parse_config();
+    let lexer = Lexer::new(input, config.lexer);
+    let tokens = if number_words && config.natural_language {
+        words::number_words(input, lexer.collect(), &|word| options.resolve(word))?
+    } else {
+        lexer.collect()
+    };
+    Ok(tokens
+        .into_iter()
+        .filter(|token| token.kind != TokenKind::Junk || !token.text(input).trim().is_empty())
+        .collect())
+}
+
+fn is_value(token: &Token) -> bool {
+    matches!(
+        token.kind,
+        TokenKind::Number { .. } | TokenKind::WordNumber(_)
+    )
+}
+
+/// Split `input` around the word `word`, if it contains it.
+fn split_at_word<'a>(input: &'a str, tokens: &[Token], word: &str) -> Option<(&'a str, &'a str)> {
+    tokens
+        .iter()
+        .find(|token| token.kind == TokenKind::Word && token.text(input).eq_ignore_ascii_case(word))
+        .map(|token| (&input[..token.start], &input[token.end..]))
+}
+
+/// Split `input` around a hyphen or dash between two values, as in `"5-10 minutes"`.
+fn split_at_dash<'a>(input: &'a str, tokens: &[Token]) -> Option<(&'a str, &'a str)> {
+    for (index, token) in tokens.iter().enumerate().skip(1) {
+        let previous = &tokens[index - 1];
+        if !is_value(previous) && previous.kind != TokenKind::Word {
+            continue;
+        }
+        let text = token.text(input);
+        let dash = match token.kind {
+            // The lexer reads "5-10" as 5 and -10.
+            TokenKind::Number { .. } if text.starts_with('-') => token.start,
+            TokenKind::Junk if matches!(text.trim(), "-" | "–") && index + 1 < tokens.len() => {
+                token.start + text.find(['-', '–']).unwrap_or(0)
+            }
+            _ => continue,
+        };
+        let after = dash + input[dash..].chars().next().map_or(0, char::len_utf8);
+        return Some((&input[..dash], &input[after..]));
+    }
+    None
+}
+
+/// Parse one end of a range. A value without a unit, like the 5 in `"5-10 minutes"`,
+/// gets the unit of the other end.
+fn parse_end(input: &str, other: &str, options: &Options) -> Result<Duration, Error> {
+    let tokens = significant_tokens(input, options, true)?;
+    if let [value] = tokens.as_slice() {
+        if is_value(value) {
+            let unit = significant_tokens(other, options, false)?
+                .into_iter()
+                .find(|token| {
+                    token.kind == TokenKind::Word && options.resolve(token.text(other)).is_some()
+                });
+            if let Some(unit) = unit {
+                let input = format!("{} {}", input.trim(), unit.text(other));
+                return parse_with_units(&input, options.parse_config(), |word| {
+                    options.resolve(word)
+                })
+                .map_err(|error| options.check_unit(error));
+            }
+        }
+    }
+    parse_with_units(input, options.parse_config(), |word| options.resolve(word))
+        .map_err(|error| options.check_unit(error))
+}
+
+/// Parse both ends of a range, where an empty end is open.
+fn parse_ends(
+    min: &str,
+    max: &str,
+    max_inclusive: bool,
+    options: &Options,
+) -> Result<(Bound<Duration>, Bound<Duration>), Error> {
+    let min_bound = if min.trim().is_empty() {
+        Bound::Unbounded
+    } else {
+        Bound::Included(parse_end(min, max, options)?)
+    };
+    let max_bound = if max.trim().is_empty() {
+        Bound::Unbounded
+    } else if max_inclusive {
+        Bound::Included(parse_end(max, min, options)?)
+    } else {
+        Bound::Excluded(parse_end(max, min, options)?)
+    };
+    Ok((min_bound, max_bound))
+}
+
+/// Parse a range of durations, such as `"5-10 minutes"`, `"1h to 2h"` or `">= 30s"`.
+///
+/// These forms are accepted, where each end is parsed like [`parse`](../parse/fn.parse.html):
+///
+/// * `"5..10s"`, excluding the end, and `"5..=10s"`, including it. Either end may be left out.
+/// * `"5-10 minutes"` and `"5 to 10 minutes"`, including both ends.
+/// * `"between 1h and 2h"`, including both ends.
+/// * `">= 30s"`, `"> 30s"`, `"<= 1h"` and `"< 1h"`, which are open on one side.
+///
+/// If one end is a value without a unit, it gets the unit of the other end,
+/// so `"5-10 minutes"` is 5 to 10 minutes, not 5 seconds to 10 minutes.
+/// A `-` directly after a value or unit is read as a range, not as a negative sign.
+///
+/// Ranges that contain no duration at all, like `"2h to 1h"`, are an
+/// [`Error::InvalidRange`](../parse/enum.Error.html#variant.InvalidRange).
+///
+/// ```
+/// use ::parse_duration0::parse_range;
+/// use ::std::ops::Bound;
+/// use ::std::time::Duration;
+///
+/// let range = parse_range("5-10 minutes").unwrap();
+/// assert_eq!(range.min, Bound::Included(Duration::new(300, 0)));
+/// assert_eq!(range.max, Bound::Included(Duration::new(600, 0)));
+///
+/// let range = parse_range(">= 30s").unwrap();
+/// assert!(range.contains(&Duration::new(60, 0)));
+/// assert!(!range.contains(&Duration::new(10, 0)));
+/// ```
+pub fn parse_range(input: &str) -> Result<DurationRange, Error> {
+    parse_range_with(input, &Options::new())
+}
+
+/// Like [`parse_range`](fn.parse_range.html), with the given options.
+///
+/// The minimum, maximum and zero checks of the options are not used for ranges.
+/// The maximum length applies to the whole range, and the maximum number of components to each end.
+pub fn parse_range_with(input: &str, options: &Options) -> Result<DurationRange, Error> {
+    options.check_length(input)?;
+    let trimmed = input.trim();
+    // The prefix, whether it sets the minimum, and whether it includes the value.
+    let comparisons = [
+        (">=", true, true),
+        ("≥", true, true),
+        (">", true, false),
+        ("<=", false, true),
+        ("≤", false, true),
+        ("<", false, false),
+    ];
+    let comparison = comparisons.iter().find_map(|&(prefix, min, inclusive)| {
+        trimmed
+            .strip_prefix(prefix)
+            .map(|rest| (rest, min, inclusive))
+    });
+    let found = if let Some((rest, min, inclusive)) = comparison {
+        let value = parse_end(rest, "", options)?;
+        let bound = if inclusive {
+            Bound::Included(value)
+        } else {
+            Bound::Excluded(value)
+        };
+        if min {
+            range(bound, Bound::Unbounded)
+        } else {
+            range(Bound::Unbounded, bound)
+        }
+    } else if let Some(separator) = trimmed.find("..") {
+        let (min, max) = (&trimmed[..separator], &trimmed[separator + 2..]);
+        let (max, inclusive) = match max.strip_prefix('=') {
+            Some(max) => (max, true),
+            None => (max, false),
+        };
+        let (min, max) = parse_ends(min, max, inclusive, options)?;
+        range(min, max)
+    } else {
+        // Without number words, so that "and" in "between five and ten" stays a word.
+        let tokens = significant_tokens(trimmed, options, false)?;
+        let between = tokens.first().is_some_and(|token| {
+            token.kind == TokenKind::Word && token.text(trimmed).eq_ignore_ascii_case("between")
+        });
+        let sides = if between {
+            let rest = &trimmed[tokens[0].end..];
+            split_at_word(rest, &significant_tokens(rest, options, false)?, "and")
+        } else {
+            split_at_word(trimmed, &tokens, "to").or_else(|| split_at_dash(trimmed, &tokens))
+        };
+        let (min, max) = sides.ok_or_else(|| Error::InvalidRange(input.to_owned()))?;
+        if min.trim().is_empty() || max.trim().is_empty() {
+            return Err(Error::InvalidRange(input.to_owned()));
+        }
+        let (min, max) = parse_ends(min, max, true, options)?;
+        range(min, max)
+    };
+    if found.is_empty() {
+        return Err(Error::InvalidRange(input.to_owned()));
+    }
+    Ok(found)
+}
+
+fn range(min: Bound<Duration>, max: Bound<Duration>) -> DurationRange {
+    DurationRange { min, max }
+}
@@ -0,0 +1,158 @@
+use crate::parse::{CasePolicy, Unit};
+
+/// A whitelist of the spellings of units, for [`Options::spellings`](../options/struct.Options.html#method.spellings).
+///
+/// By default, any initial segment of the name of a unit is accepted, like `"mill"` or `"hou"`.
+/// With `Spellings`, only the listed words are. A spelling in lower case, like `"min"`, also
+/// matches other cases according to the [`CasePolicy`](../parse/enum.CasePolicy.html), but one
+/// with a capital, like `"M"`, must be written exactly, unless the policy is case-insensitive.
+///
+/// ```
+/// use ::parse_duration0::{parse_with, Error, Options, Spellings, Unit};
+/// use ::std::time::Duration;
+///
+/// let options = Options::new().spellings(Spellings::canonical_short());
+/// assert_eq!(parse_with("1h 30m", &options), Ok(Duration::new(5_400, 0)));
+/// assert_eq!(parse_with("1 hou", &options), Err(Error::UnknownUnit("hou".to_string())));
+///
+/// let spellings = Spellings::new().unit(Unit::Minute, &["min", "mins"]);
+/// let options = Options::new().spellings(spellings);
+/// assert_eq!(parse_with("5 mins", &options), Ok(Duration::new(300, 0)));
+/// assert_eq!(parse_with("5 m", &options), Err(Error::UnknownUnit("m".to_string())));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Spellings {
+    words: Vec<(String, Unit)>,
+}
+
+impl Spellings {
+    /// An empty whitelist, which accepts no units at all.
+    pub fn new() -> Self {
+        Spellings::default()
+    }
+
+    /// Also accept each of `words` for `unit`.
+    pub fn unit(mut self, unit: Unit, words: &[&str]) -> Self {
+        self.words
+            .extend(words.iter().map(|&word| (word.to_owned(), unit)));
+        self
+    }
+
+    /// The usual symbols: `"ns"`, `"us"` or `"µs"`, `"ms"`, `"s"`, `"m"`, `"h"`, `"d"`, `"w"`,
+    /// `"M"` for months, `"y"`, `"ks"`, `"Ms"` and `"Gs"`.
+    /// Units without a symbol, like fortnights, are not accepted.
+    pub fn canonical_short() -> Self {
+        Spellings::new()
+            .unit(Unit::Nanosecond, &["ns"])
+            .unit(Unit::Microsecond, &["us", "\u{b5}s", "\u{3bc}s"])
+            .unit(Unit::Millisecond, &["ms"])
+            .unit(Unit::Second, &["s"])
+            .unit(Unit::Minute, &["m"])
+            .unit(Unit::Hour, &["h"])
+            .unit(Unit::Day, &["d"])
+            .unit(Unit::Week, &["w"])
+            .unit(Unit::Month, &["M"])
+            .unit(Unit::Year, &["y"])
+            .unit(Unit::Kilosecond, &["ks"])
+            .unit(Unit::Megasecond, &["Ms"])
+            .unit(Unit::Gigasecond, &["Gs"])
+    }
+
+    /// The full names of all units, in singular and plural, like `"minute"` and `"minutes"`.
+    pub fn canonical_long() -> Self {
+        Spellings::new()
+            .unit(Unit::Nanosecond, &["nanosecond", "nanoseconds"])
+            .unit(Unit::Microsecond, &["microsecond", "microseconds"])
+            .unit(Unit::Millisecond, &["millisecond", "milliseconds"])
+            .unit(Unit::Second, &["second", "seconds"])
+            .unit(Unit::Minute, &["minute", "minutes"])
+            .unit(Unit::Hour, &["hour", "hours"])
+            .unit(Unit::Day, &["day", "days"])
+            .unit(Unit::Week, &["week", "weeks"])
+            .unit(Unit::Month, &["month", "months"])
+            .unit(Unit::Year, &["year", "years"])
+            .unit(Unit::Fortnight, &["fortnight", "fortnights"])
+            .unit(Unit::Quarter, &["quarter", "quarters"])
+            .unit(Unit::Decade, &["decade", "decades"])
+            .unit(Unit::Century, &["century", "centuries"])
+            .unit(Unit::Jiffy, &["jiffy", "jiffies"])
+            .unit(Unit::Kilosecond, &["kilosecond", "kiloseconds"])
+            .unit(Unit::Megasecond, &["megasecond", "megaseconds"])
+            .unit(Unit::Gigasecond, &["gigasecond", "gigaseconds"])
+    }
+
+    /// The spellings of [systemd.time](https://www.freedesktop.org/software/systemd/man/systemd.time.html#Parsing%20Time%20Spans).
+    ///
+    /// Note that systemd has years of 365.25 days, which
+    /// [`Calendar::Julian`](../parse/enum.Calendar.html#variant.Julian) gives.
+    pub fn systemd() -> Self {
+        Spellings::new()
+            .unit(Unit::Nanosecond, &["nsec", "ns"])
+            .unit(Unit::Microsecond, &["usec", "us", "\u{b5}s", "\u{3bc}s"])
+            .unit(Unit::Millisecond, &["msec", "ms"])
+            .unit(Unit::Second, &["seconds", "second", "sec", "s"])
+            .unit(Unit::Minute, &["minutes", "minute", "min", "m"])
+            .unit(Unit::Hour, &["hours", "hour", "hr", "h"])
+            .unit(Unit::Day, &["days", "day", "d"])
+            .unit(Unit::Week, &["weeks", "week", "w"])
+            .unit(Unit::Month, &["months", "month", "M"])
+            .unit(Unit::Year, &["years", "year", "y"])
+    }
+
+    fn find(&self, matches: impl Fn(&str) -> bool) -> Option<Unit> {
+        self.words
+            .iter()
+            .find(|(spelling, _)| matches(spelling))
+            .map(|&(_, unit)| unit)
+    }
+
+    /// Find the unit for a word with the case policy, or `None` if it is not in the whitelist.
+    pub(crate) fn resolve(&self, word: &str, policy: CasePolicy) -> Option<Unit> {
+        let exact = self.find(|spelling| spelling == word);
+        let mixed = || {
+            exact.or_else(|| {
+                self.find(|spelling| !has_upper_case(spelling) && eq_ignore_case(spelling, word))
+            })
+        };
+        match policy {
+            CasePolicy::Sensitive => exact,
+            CasePolicy::Mixed => mixed(),
+            CasePolicy::Insensitive => {
+                exact.or_else(|| self.find(|spelling| eq_ignore_case(spelling, word)))
+            }
+            CasePolicy::RejectAmbiguous => {
+                let unit = mixed()?;
+                let ambiguous = self
+                    .words
+                    .iter()
+                    .any(|(spelling, other)| *other != unit && eq_ignore_case(spelling, word));
+                if ambiguous {
+                    None
+                } else {
+                    Some(unit)
+                }
+            }
+        }
+    }
+
+    /// The different units of the spellings that match `word` when case is ignored.
+    pub(crate) fn candidates(&self, word: &str) -> Vec<Unit> {
+        let mut candidates = Vec::new();
+        for &(ref spelling, unit) in &self.words {
+            if eq_ignore_case(spelling, word) && !candidates.contains(&unit) {
+                candidates.push(unit);
+            }
+        }
+        candidates
+    }
+}
+
+fn has_upper_case(word: &str) -> bool {
+    word.chars().any(char::is_uppercase)
+}
+
+fn eq_ignore_case(a: &str, b: &str) -> bool {
+    a.chars()
+        .flat_map(char::to_lowercase)
+        .eq(b.chars().flat_map(char::to_lowercase))
+}
@@ -1,3 +1,6 @@
+// Some of the older tests use constants like `::std::i64::MAX`.
+#![allow(clippy::legacy_numeric_constants)]
+
 use ::std::time::Duration;
 
 use super::parse;
@@ -132,6 +135,30 @@ test_invalid!(fn year_exp4("1e5 y", parse::Error::ExpNotSupported));
 test_invalid!(fn year_exp5("1e+5 y", parse::Error::ExpNotSupported));
 test_invalid!(fn year_exp6("1e-5 y", parse::Error::ExpNotSupported));
 
+test_parse!(fn fortnight1("1fortnights", 1_209_600, 0));
+test_parse!(fn fortnight2("1 Fortnight", 1_209_600, 0));
+test_parse!(fn quarter1("1quarters", 7_889_238, 0));
+test_parse!(fn quarter2("1 qtr", 7_889_238, 0));
+test_parse!(fn decade1("1decades", 315_569_520, 0));
+test_parse!(fn decade_dec("1.5 decade", 473_354_280, 0));
+test_parse!(fn century1("1century", 3_155_695_200, 0));
+test_parse!(fn century2("2 CENTURIES", 6_311_390_400, 0));
+test_parse!(fn jiffy1("1jiffy", 0, 10_000_000));
+test_parse!(fn jiffy2("250 jiffies", 2, 500_000_000));
+test_parse!(fn kilosecond1("1ks", 1_000, 0));
+test_parse!(fn kilosecond2("1.5 kiloseconds", 1_500, 0));
+test_parse!(fn megasecond1("1Ms", 1_000_000, 0));
+test_parse!(fn megasecond2("1 megasecond", 1_000_000, 0));
+test_parse!(fn gigasecond1("1Gs", 1_000_000_000, 0));
+test_parse!(fn gigasecond2("1 gsec", 1_000_000_000, 0));
+test_parse!(fn megasecond_case_ms("1ms", 0, 1_000_000));
+test_parse!(fn megasecond_case_upper("1MS", 0, 1_000_000));
+test_parse!(fn megasecond_case_month("1Mo", 2_629_746, 0));
+test_invalid!(fn decade_prefix("1 de", parse::Error::UnknownUnit("de".to_owned())));
+test_invalid!(fn century_prefix("1 c", parse::Error::UnknownUnit("c".to_owned())));
+test_invalid!(fn fortnight_prefix("1 fort", parse::Error::UnknownUnit("fort".to_owned())));
+test_invalid!(fn century_overflow("100000000000000000 centuries", parse::Error::Overflow));
+
 test_parse!(fn multi_with_space("1min    10 seconds", 70, 0));
 test_parse!(fn multi_no_space("1min10seconds", 70, 0));
 test_parse!(fn multi_out_of_order("10year1min10seconds5h", 315_587_590, 0));
@@ -141,6 +168,9 @@ test_parse!(fn multiple_units("16 min seconds", 960, 0));
 
 test_parse!(fn negatives("1 day -15 minutes", 85_500, 0));
 test_parse!(fn unmatched_negatives("1 day - 15 minutes", 87_300, 0));
+test_parse!(fn negative_fraction("1 minute -0.5 seconds", 59, 500_000_000));
+test_parse!(fn negative_decimal("1 day -1.5 hours", 81_000, 0));
+test_invalid!(fn negative_milliseconds("-1 ms", parse::Error::OutOfBounds(-1)));
 
 test_parse!(fn no_unit("15", 15, 0));
 test_parse!(fn no_unit_with_noise(".:++++]][][[][15[]][][]:}}}}", 15, 0));
@@ -175,3 +205,2927 @@ fn negative_duration() {
 }
 
 test_invalid!(fn not_enough_units("16 17 seconds", parse::Error::NoUnitFound("16".to_string())));
+test_invalid!(fn unit_sum_overflow("9223372036854775807 s 1 s", parse::Error::Overflow));
+test_invalid!(fn total_overflow("9223372036854775807 years", parse::Error::Overflow));
+
+mod localized {
+    use ::std::time::Duration;
+
+    use crate::format::format;
+    use crate::locale::{self, parse_localized};
+    use crate::parse::Error;
+
+    #[test]
+    fn english_matches_parse() {
+        for input in &[
+            "1 day -1 hour",
+            "15days20seconds100milliseconds",
+            "1.07 M",
+            "15",
+        ] {
+            assert_eq!(parse_localized(&locale::EN, input), super::parse(input));
+        }
+    }
+
+    #[test]
+    fn english_vulgar_fraction() {
+        assert_eq!(
+            parse_localized(&locale::EN, "1½ hours"),
+            Ok(Duration::new(5_400, 0))
+        );
+    }
+
+    #[test]
+    fn english_unknown_unit() {
+        assert_eq!(
+            parse_localized(&locale::EN, "3 jours"),
+            Err(Error::UnknownUnit("jours".to_owned()))
+        );
+    }
+
+    #[test]
+    fn english_format() {
+        assert_eq!(format(Duration::new(0, 0)), "0 seconds");
+        assert_eq!(format(Duration::new(60, 0)), "1 minute");
+        assert_eq!(
+            format(Duration::new(90_061, 1_001_001)),
+            "1 day 1 hour 1 minute 1 second 1 millisecond 1 microsecond and 1 nanosecond"
+        );
+    }
+
+    #[test]
+    fn english_roundtrip() {
+        let duration = Duration::new(1_234_567, 89);
+        assert_eq!(super::parse(&format(duration)), Ok(duration));
+    }
+
+    #[cfg(feature = "locale-de")]
+    #[test]
+    fn german() {
+        assert_eq!(
+            parse_localized(&locale::DE, "2 Stunden und 30 Minuten"),
+            Ok(Duration::new(9_000, 0))
+        );
+        assert_eq!(
+            parse_localized(&locale::DE, "in 3 tagen"),
+            Ok(Duration::new(259_200, 0))
+        );
+        assert_eq!(
+            crate::format::format_localized(&locale::DE, Duration::new(9_000, 0)),
+            "2 Stunden und 30 Minuten"
+        );
+    }
+
+    #[cfg(feature = "locale-fr")]
+    #[test]
+    fn french() {
+        assert_eq!(
+            parse_localized(&locale::FR, "3 jours"),
+            Ok(Duration::new(259_200, 0))
+        );
+        assert_eq!(
+            parse_localized(&locale::FR, "1 an et 2 mois"),
+            Ok(Duration::new(36_816_444, 0))
+        );
+        assert_eq!(
+            crate::format::format_localized(&locale::FR, Duration::new(3_601, 0)),
+            "1 heure et 1 seconde"
+        );
+    }
+
+    #[cfg(feature = "locale-es")]
+    #[test]
+    fn spanish() {
+        assert_eq!(
+            parse_localized(&locale::ES, "2 DÍAS y 1 hora"),
+            Ok(Duration::new(176_400, 0))
+        );
+        assert_eq!(
+            crate::format::format_localized(&locale::ES, Duration::new(176_400, 0)),
+            "2 días y 1 hora"
+        );
+    }
+
+    #[cfg(feature = "locale-nl")]
+    #[test]
+    fn dutch() {
+        assert_eq!(
+            parse_localized(&locale::NL, "1u 30 min"),
+            Ok(Duration::new(5_400, 0))
+        );
+        assert_eq!(
+            crate::format::format_localized(&locale::NL, Duration::new(7_260, 0)),
+            "2 uur en 1 minuut"
+        );
+    }
+
+    #[cfg(feature = "locale-ja")]
+    #[test]
+    fn japanese() {
+        assert_eq!(
+            parse_localized(&locale::JA, "5 分"),
+            Ok(Duration::new(300, 0))
+        );
+        assert_eq!(
+            parse_localized(&locale::JA, "2時間30分"),
+            Ok(Duration::new(9_000, 0))
+        );
+        assert_eq!(
+            parse_localized(&locale::JA, "1時間と30分"),
+            Ok(Duration::new(5_400, 0))
+        );
+        assert_eq!(
+            crate::format::format_localized(&locale::JA, Duration::new(9_000, 0)),
+            "2時間30分"
+        );
+    }
+}
+
+mod separators {
+    use ::std::time::Duration;
+
+    use crate::options::{parse_with, Options};
+    use crate::parse::Error;
+
+    #[test]
+    fn default_splits_on_comma() {
+        assert_eq!(
+            parse_with("1,5 h", &Options::new()),
+            Err(Error::NoUnitFound("1".to_owned()))
+        );
+    }
+
+    #[test]
+    fn decimal_comma() {
+        let options = Options::new().decimal_separator(',');
+        assert_eq!(parse_with("1,5 h", &options), Ok(Duration::new(5_400, 0)));
+        assert_eq!(
+            parse_with("1 hour, 15 minutes", &options),
+            Ok(Duration::new(4_500, 0))
+        );
+        assert_eq!(
+            parse_with("1.5 h", &options),
+            Err(Error::NoUnitFound("1".to_owned()))
+        );
+    }
+
+    #[test]
+    fn underscores() {
+        let options = Options::new().group_separators(&['_']);
+        assert_eq!(
+            parse_with("1_000_000 ns", &options),
+            Ok(Duration::new(0, 1_000_000))
+        );
+        assert_eq!(parse_with("1_000", &options), Ok(Duration::new(1_000, 0)));
+        assert_eq!(
+            parse_with("1.000_5 s", &options),
+            Ok(Duration::new(1, 500_000))
+        );
+        assert_eq!(
+            parse_with("1_ s", &options),
+            Err(Error::UnknownUnit("_".to_owned()))
+        );
+    }
+
+    #[test]
+    fn spaces_and_dots() {
+        let options = Options::new()
+            .decimal_separator(',')
+            .group_separators(&['.', ' ', '\u{a0}']);
+        assert_eq!(
+            parse_with("1 000 000 ms", &options),
+            Ok(Duration::new(1_000, 0))
+        );
+        assert_eq!(
+            parse_with("1.000,5 s", &options),
+            Ok(Duration::new(1_000, 500_000_000))
+        );
+        assert_eq!(
+            parse_with("1\u{a0}000 s", &options),
+            Ok(Duration::new(1_000, 0))
+        );
+    }
+
+    #[test]
+    fn space_before_fraction_is_mixed_number() {
+        let options = Options::new().group_separators(&[' ']);
+        assert_eq!(
+            parse_with("1 000 1/2 s", &options),
+            Ok(Duration::new(1_000, 500_000_000))
+        );
+    }
+
+    #[test]
+    fn group_separator_is_not_decimal_separator() {
+        let options = Options::new().group_separators(&['.']);
+        assert_eq!(
+            parse_with("1.5 s", &options),
+            Ok(Duration::new(1, 500_000_000))
+        );
+    }
+
+    #[test]
+    fn invalid_grouped_number() {
+        let options = Options::new().group_separators(&['_']);
+        assert_eq!(
+            parse_with("99_999_999_999_999_999_999 s", &options),
+            Err(Error::ParseInt("99_999_999_999_999_999_999".to_owned()))
+        );
+    }
+
+    #[cfg(feature = "locale-de")]
+    #[test]
+    fn locale_decimal_separator() {
+        use crate::locale::{parse_localized, DE};
+        assert_eq!(
+            parse_localized(&DE, "1,5 Stunden"),
+            Ok(Duration::new(5_400, 0))
+        );
+        let options = Options::new().locale(&DE).decimal_separator('.');
+        assert_eq!(
+            parse_with("1.5 Stunden", &options),
+            Ok(Duration::new(5_400, 0))
+        );
+    }
+}
+
+mod natural_language {
+    use ::std::time::Duration;
+
+    use crate::options::{parse_with, Options};
+    use crate::parse::Error;
+
+    macro_rules! test_words {
+        (fn $fun:ident($string: expr, $seconds: expr, $nanoseconds: expr)) => {
+            #[test]
+            fn $fun() {
+                assert_eq!(
+                    parse_with($string, &Options::new().natural_language(true)),
+                    Ok(Duration::new($seconds, $nanoseconds))
+                )
+            }
+        };
+    }
+
+    test_words!(fn cardinal("remind me in twenty minutes", 1_200, 0));
+    test_words!(fn cardinal_compound("twenty-five seconds", 25, 0));
+    test_words!(fn cardinal_hundred("one hundred and five seconds", 105, 0));
+    test_words!(fn cardinal_hundred_compound("one hundred twenty five s", 125, 0));
+    test_words!(fn cardinal_thousand("two thousand three hundred ms", 2, 300_000_000));
+    test_words!(fn cardinal_hundred_thousand("a hundred thousand s", 100_000, 0));
+    test_words!(fn article("a week", 604_800, 0));
+    test_words!(fn article_an("An hour", 3_600, 0));
+    test_words!(fn article_multiplier("a dozen seconds", 12, 0));
+    test_words!(fn article_not_value("take a break for 5 minutes", 300, 0));
+    test_words!(fn half_an_hour("half an hour", 1_800, 0));
+    test_words!(fn a_half_hour("a half hour", 1_800, 0));
+    test_words!(fn quarter_of_an_hour("a quarter of an hour", 900, 0));
+    test_words!(fn three_quarters("three quarters of an hour", 2_700, 0));
+    test_words!(fn quarter_hour("a quarter hour", 900, 0));
+    test_words!(fn quarters_unit("2 quarters", 15_778_476, 0));
+    test_words!(fn quarter_unit("1 quarter", 7_889_238, 0));
+    test_words!(fn a_quarter_unit("a quarter", 7_889_238, 0));
+    test_words!(fn quarters_unit_words("three quarters", 23_667_714, 0));
+    test_words!(fn and_a_half("two and a half days", 216_000, 0));
+    test_words!(fn digits_and_a_half("2 and a half hours", 9_000, 0));
+    test_words!(fn unit_and_a_half("an hour and a half", 5_400, 0));
+    test_words!(fn unit_and_a_quarter("1 day and a quarter", 108_000, 0));
+    test_words!(fn mixed("one hour and 15 minutes", 4_500, 0));
+    test_words!(fn digits_unchanged("1 day -1 hour", 82_800, 0));
+
+    #[test]
+    fn disabled_by_default() {
+        assert_eq!(
+            parse_with("twenty minutes", &Options::new()),
+            Err(Error::NoValueFound("twenty minutes".to_owned()))
+        );
+    }
+
+    #[test]
+    fn word_without_unit() {
+        assert_eq!(
+            parse_with("twenty", &Options::new().natural_language(true)),
+            Err(Error::NoUnitFound("twenty".to_owned()))
+        );
+    }
+
+    #[test]
+    fn word_unknown_unit() {
+        assert_eq!(
+            parse_with("two eons", &Options::new().natural_language(true)),
+            Err(Error::UnknownUnit("eons".to_owned()))
+        );
+    }
+
+    #[test]
+    fn malformed_cardinal() {
+        let options = Options::new().natural_language(true);
+        for (input, first) in [
+            ("one two minutes", "one"),
+            ("five five seconds", "five"),
+            ("twenty twenty seconds", "twenty"),
+            ("fifteen five seconds", "fifteen"),
+            ("twenty five two seconds", "twenty five"),
+        ] {
+            assert_eq!(
+                parse_with(input, &options),
+                Err(Error::NoUnitFound(first.to_owned()))
+            );
+        }
+    }
+
+    #[test]
+    fn repeated_multiplier() {
+        let options = Options::new().natural_language(true);
+        for input in [
+            "one million million million million hours",
+            "one thousand hundred seconds",
+        ] {
+            assert_eq!(
+                parse_with(input, &options),
+                Err(Error::InvalidFormat(input.to_owned()))
+            );
+        }
+    }
+}
+
+test_parse!(fn fraction_half("1/2 hour", 1_800, 0));
+test_parse!(fn fraction_three_quarters("3/4 day", 64_800, 0));
+test_parse!(fn fraction_thirds("1/3 minute", 20, 0));
+test_parse!(fn fraction_rounds_down("1/3 s", 0, 333_333_333));
+test_parse!(fn fraction_negative("1 day -1/2 day", 43_200, 0));
+test_parse!(fn fraction_improper("7/2h", 12_600, 0));
+test_parse!(fn fraction_slash("1\u{2044}2 h", 1_800, 0));
+test_parse!(fn mixed_fraction("1 1/2 hours", 5_400, 0));
+test_parse!(fn mixed_fraction_negative("2 h -1 1/2 h", 1_800, 0));
+test_parse!(fn mixed_fraction_slash("1 1\u{2044}4 min", 75, 0));
+test_parse!(fn mixed_fraction_sum("1 h 1 1/2 min", 3_690, 0));
+test_parse!(fn vulgar_fraction("½ h", 1_800, 0));
+test_parse!(fn vulgar_fraction_mixed("1½ h", 5_400, 0));
+test_parse!(fn vulgar_fraction_negative("1 h -¼h", 2_700, 0));
+test_parse!(fn vulgar_fraction_thirds("⅔ min", 40, 0));
+test_parse!(fn decimal_large_unit("776.4 months", 2_041_734_794, 400_000_000));
+test_parse!(fn decimal_years("300.5y", 9_482_864_076, 0));
+test_parse!(fn decimal_years_long("292.5 years", 9_230_408_460, 0));
+test_parse!(fn decimal_seconds_past_i64_nanoseconds("9300000000.5 s", 9_300_000_000, 500_000_000));
+test_invalid!(fn fraction_zero_denominator("1/0 h", parse::Error::DivisionByZero("1/0".to_string())));
+test_invalid!(fn fraction_no_unit("1/2", parse::Error::NoUnitFound("1/2".to_string())));
+test_invalid!(fn mixed_fraction_no_unit("1 1/2", parse::Error::NoUnitFound("1 1/2".to_string())));
+test_invalid!(fn mixed_fraction_zero_denominator("1 1/0 h", parse::Error::DivisionByZero("1 1/0".to_string())));
+test_invalid!(fn fraction_exponent("1/2e5 s", parse::Error::ExpNotSupported));
+
+mod expression {
+    use crate::parse::Error;
+    use crate::{parse_expression, parse_expression_with, Options, SpannedError};
+    use ::std::time::Duration;
+
+    macro_rules! test_expression {
+        (fn $fun:ident($string: expr, $seconds: expr, $nanoseconds: expr)) => {
+            #[test]
+            fn $fun() {
+                assert_eq!(
+                    parse_expression($string),
+                    Ok(Duration::new($seconds, $nanoseconds))
+                )
+            }
+        };
+    }
+
+    macro_rules! test_expression_invalid {
+        (fn $fun:ident($string: expr, $error: expr, $span: expr)) => {
+            #[test]
+            fn $fun() {
+                assert_eq!(
+                    parse_expression($string),
+                    Err(SpannedError {
+                        error: $error,
+                        span: $span
+                    })
+                );
+            }
+        };
+    }
+
+    test_expression!(fn single("1h 30m", 5_400, 0));
+    test_expression!(fn subtract("2h - 15m", 6_300, 0));
+    test_expression!(fn subtract_without_space("2h -15m", 6_300, 0));
+    test_expression!(fn subtract_compact("2h-15m", 6_300, 0));
+    test_expression!(fn add("1h + 1h 30m", 9_000, 0));
+    test_expression!(fn multiply("3 * 45s", 135, 0));
+    test_expression!(fn multiply_sign("45s × 3", 135, 0));
+    test_expression!(fn divide("(1d + 2h) / 2", 46_800, 0));
+    test_expression!(fn divide_sign("1h ÷ 4", 900, 0));
+    test_expression!(fn divide_compact("1h/3", 1_200, 0));
+    test_expression!(fn precedence("1h + 2 * 15m", 5_400, 0));
+    test_expression!(fn parentheses("(1 + 2) * 15m", 2_700, 0));
+    test_expression!(fn negative_parentheses("1h - (10m + 5m)", 2_700, 0));
+    test_expression!(fn unary_minus("-15m + 1h", 2_700, 0));
+    test_expression!(fn ratio_is_seconds("1h / 30m", 2, 0));
+    test_expression!(fn scalar_is_seconds("3 * 1.5", 4, 500_000_000));
+    test_expression!(fn fraction("1/2 h + 1.5 h", 7_200, 0));
+    test_expression!(fn mixed_fraction("2 * 1 1/2 h", 10_800, 0));
+    test_expression!(fn division_rounds_down("1s / 3", 0, 333_333_333));
+
+    test_expression_invalid!(fn empty("", Error::NoValueFound(String::new()), 0..0));
+    test_expression_invalid!(fn unexpected_end("1h +", Error::UnexpectedEnd, 4..4));
+    test_expression_invalid!(fn unclosed("(1h + 2h", Error::UnexpectedEnd, 8..8));
+    test_expression_invalid!(fn unexpected_parenthesis("1h)", Error::UnexpectedToken(")".to_string()), 2..3));
+    test_expression_invalid!(fn unexpected_character("1h % 2", Error::UnexpectedToken("%".to_string()), 3..4));
+    test_expression_invalid!(fn unexpected_number("1h 30", Error::UnexpectedToken("30".to_string()), 3..5));
+    test_expression_invalid!(fn unknown_unit("1h + 2 lightyears", Error::UnknownUnit("lightyears".to_string()), 7..17));
+    test_expression_invalid!(fn exponent("1e5 s", Error::ExpNotSupported, 0..3));
+    test_expression_invalid!(fn divide_by_zero("1h / (2 - 2)", Error::DivisionByZero("(2 - 2)".to_string()), 5..12));
+    test_expression_invalid!(fn divide_by_zero_duration("1h / 0s", Error::DivisionByZero("0s".to_string()), 5..7));
+    test_expression_invalid!(fn multiply_durations("1h * 2h", Error::InvalidOperation("cannot multiply two durations".to_string()), 0..7));
+    test_expression_invalid!(fn add_number("1h + 2", Error::InvalidOperation("cannot add or subtract a number and a duration".to_string()), 0..6));
+    test_expression_invalid!(fn divide_number("2 / 1h", Error::InvalidOperation("cannot divide a number by a duration".to_string()), 0..6));
+    test_expression_invalid!(fn negative("15m - 1h", Error::OutOfBounds(-2_700), 0..8));
+    test_expression_invalid!(fn overflow("9223372036854775807 * 9223372036854775807 years", Error::Overflow, 0..47));
+
+    // The product is i128::MIN nanoseconds.
+    const MIN: &str = "(-4611686018427387904ns*4294967296*4294967296*2)";
+
+    #[test]
+    fn overflow_divide_minimum() {
+        let input = format!("{}/-1ns", MIN);
+        assert_eq!(
+            parse_expression_with(&input, &Options::hardened()).map_err(|e| e.error),
+            Err(Error::Overflow)
+        );
+    }
+
+    #[test]
+    fn overflow_negate_minimum() {
+        let input = format!("-{}", MIN);
+        assert_eq!(
+            parse_expression(&input).map_err(|e| e.error),
+            Err(Error::Overflow)
+        );
+    }
+
+    #[test]
+    fn overflow_divide_by_minimum() {
+        let input = format!("0ns / {}", MIN);
+        assert_eq!(
+            parse_expression(&input).map_err(|e| e.error),
+            Err(Error::Overflow)
+        );
+    }
+
+    #[test]
+    fn nesting_too_deep() {
+        let input = format!("{}1h{}", "(".repeat(100), ")".repeat(100));
+        assert_eq!(
+            parse_expression(&input).map_err(|e| e.error),
+            Err(Error::NestingTooDeep)
+        );
+    }
+
+    #[test]
+    fn with_options() {
+        let options = Options::new().decimal_separator(',').natural_language(true);
+        assert_eq!(
+            parse_expression_with("an hour - 1,5 min", &options),
+            Ok(Duration::new(3_510, 0))
+        );
+    }
+
+    #[test]
+    fn error_display() {
+        assert_eq!(
+            parse_expression("1h ? 2").unwrap_err().to_string(),
+            "UnexpectedTokenError: did not expect \"?\" (at 3..4)"
+        );
+    }
+}
+
+mod range {
+    use crate::parse::Error;
+    use crate::{parse_range, parse_range_with, DurationRange, Options};
+    use ::std::ops::Bound::{self, Excluded, Included, Unbounded};
+    use ::std::time::Duration;
+
+    fn s(seconds: u64) -> Duration {
+        Duration::new(seconds, 0)
+    }
+
+    macro_rules! test_range {
+        (fn $fun:ident($string: expr, $min: expr, $max: expr)) => {
+            #[test]
+            fn $fun() {
+                let (min, max): (Bound<Duration>, Bound<Duration>) = ($min, $max);
+                assert_eq!(parse_range($string), Ok(DurationRange { min, max }));
+            }
+        };
+    }
+
+    macro_rules! test_range_invalid {
+        (fn $fun:ident($string: expr, $error: expr)) => {
+            #[test]
+            fn $fun() {
+                assert_eq!(parse_range($string), Err($error));
+            }
+        };
+    }
+
+    test_range!(fn dots("5..10s", Included(s(5)), Excluded(s(10))));
+    test_range!(fn dots_inclusive("5s..=10s", Included(s(5)), Included(s(10))));
+    test_range!(fn dots_open_start("..10 min", Unbounded, Excluded(s(600))));
+    test_range!(fn dots_open_end("1h..", Included(s(3_600)), Unbounded));
+    test_range!(fn dots_decimal("1.5..2.5 h", Included(s(5_400)), Excluded(s(9_000))));
+    test_range!(fn hyphen("5-10 minutes", Included(s(300)), Included(s(600))));
+    test_range!(fn hyphen_spaced("5 - 10 minutes", Included(s(300)), Included(s(600))));
+    test_range!(fn hyphen_units("1h-2h", Included(s(3_600)), Included(s(7_200))));
+    test_range!(fn en_dash("5 – 10 minutes", Included(s(300)), Included(s(600))));
+    test_range!(fn to("1h to 2h", Included(s(3_600)), Included(s(7_200))));
+    test_range!(fn to_shared_unit("30 to 90 seconds", Included(s(30)), Included(s(90))));
+    test_range!(fn to_mixed_units("90 seconds to 1 hour 30 minutes", Included(s(90)), Included(s(5_400))));
+    test_range!(fn between("between 1h and 2h", Included(s(3_600)), Included(s(7_200))));
+    test_range!(fn between_shared_unit("Between 1 and 2 days", Included(s(86_400)), Included(s(172_800))));
+    test_range!(fn at_least(">= 30s", Included(s(30)), Unbounded));
+    test_range!(fn at_least_sign("≥ 30s", Included(s(30)), Unbounded));
+    test_range!(fn more_than("> 30s", Excluded(s(30)), Unbounded));
+    test_range!(fn at_most("<= 1h", Unbounded, Included(s(3_600))));
+    test_range!(fn less_than("<1h", Unbounded, Excluded(s(3_600))));
+    test_range!(fn without_units("5..10", Included(s(5)), Excluded(s(10))));
+    test_range!(fn single_point("1h to 60m", Included(s(3_600)), Included(s(3_600))));
+
+    test_range_invalid!(fn reversed("2h to 1h", Error::InvalidRange("2h to 1h".to_string())));
+    test_range_invalid!(fn empty_exclusive("1h..1h", Error::InvalidRange("1h..1h".to_string())));
+    test_range_invalid!(fn less_than_zero("< 0s", Error::InvalidRange("< 0s".to_string())));
+    test_range_invalid!(fn no_separator("5 minutes", Error::InvalidRange("5 minutes".to_string())));
+    test_range_invalid!(fn missing_end("5 minutes to", Error::InvalidRange("5 minutes to".to_string())));
+    test_range_invalid!(fn negative_start("-5-10 s", Error::OutOfBounds(-5)));
+    test_range_invalid!(fn unknown_unit("1h to 2 blorps", Error::UnknownUnit("blorps".to_string())));
+
+    #[test]
+    fn contains() {
+        let range = parse_range("5..10s").unwrap();
+        assert!(range.contains(&s(5)));
+        assert!(range.contains(&Duration::new(9, 999_999_999)));
+        assert!(!range.contains(&s(10)));
+        assert!(!range.contains(&s(4)));
+    }
+
+    #[test]
+    fn with_options() {
+        let options = Options::new().natural_language(true);
+        assert_eq!(
+            parse_range_with("between five and ten minutes", &options),
+            Ok(DurationRange {
+                min: Included(s(300)),
+                max: Included(s(600))
+            })
+        );
+    }
+}
+
+mod bounded {
+    use crate::parse::Error;
+    use crate::{parse_bounded, parse_expression_with, parse_with, Options, Unit};
+    use ::std::time::Duration;
+
+    const SECOND: Duration = Duration::new(1, 0);
+    const DAY: Duration = Duration::new(86_400, 0);
+
+    #[test]
+    fn within() {
+        assert_eq!(
+            parse_bounded("1h", SECOND, DAY),
+            Ok(Duration::new(3_600, 0))
+        );
+        assert_eq!(parse_bounded("1s", SECOND, DAY), Ok(SECOND));
+        assert_eq!(parse_bounded("24 hours", SECOND, DAY), Ok(DAY));
+    }
+
+    #[test]
+    fn too_short() {
+        assert_eq!(
+            parse_bounded("999ms", SECOND, DAY),
+            Err(Error::TooShort {
+                min: SECOND,
+                value: Duration::new(0, 999_000_000)
+            })
+        );
+    }
+
+    #[test]
+    fn too_long() {
+        assert_eq!(
+            parse_bounded("1 day 1 ns", SECOND, DAY),
+            Err(Error::TooLong {
+                max: DAY,
+                value: Duration::new(86_400, 1)
+            })
+        );
+    }
+
+    #[test]
+    fn parse_error_first() {
+        assert_eq!(
+            parse_bounded("1 blorp", SECOND, DAY),
+            Err(Error::UnknownUnit("blorp".to_owned()))
+        );
+    }
+
+    #[test]
+    fn only_min() {
+        let options = Options::new().min(SECOND);
+        assert_eq!(
+            parse_with("10 years", &options),
+            Ok(Duration::new(315_569_520, 0))
+        );
+        assert!(parse_with("1ms", &options).is_err());
+    }
+
+    #[test]
+    fn zero() {
+        let options = Options::new().disallow_zero(true);
+        assert_eq!(parse_with("0s", &options), Err(Error::ZeroNotAllowed));
+        assert_eq!(parse_with("1h -60m", &options), Err(Error::ZeroNotAllowed));
+        assert_eq!(parse_with("1ns", &options), Ok(Duration::new(0, 1)));
+        assert_eq!(parse_with("0s", &Options::new()), Ok(Duration::new(0, 0)));
+    }
+
+    #[test]
+    fn allowed_units() {
+        let options = Options::new().allowed_units(&[Unit::Second, Unit::Minute, Unit::Hour]);
+        assert_eq!(
+            parse_with("1h 30m 5s", &options),
+            Ok(Duration::new(5_405, 0))
+        );
+        assert_eq!(
+            parse_with("1h 2 months", &options),
+            Err(Error::UnitNotAllowed {
+                word: "months".to_owned(),
+                unit: Unit::Month
+            })
+        );
+        assert_eq!(
+            parse_with("1 blorp", &options),
+            Err(Error::UnknownUnit("blorp".to_owned()))
+        );
+        assert_eq!(parse_with("15", &options), Ok(Duration::new(15, 0)));
+        let options = Options::new().allowed_units(&[Unit::Minute, Unit::Hour]);
+        assert_eq!(
+            parse_with("15", &options),
+            Err(Error::UnitNotAllowed {
+                word: "15".to_owned(),
+                unit: Unit::Second
+            })
+        );
+    }
+
+    #[test]
+    fn expression() {
+        let options = Options::new()
+            .max(DAY)
+            .allowed_units(&[Unit::Hour, Unit::Day]);
+        assert_eq!(parse_expression_with("2d - 1d", &options), Ok(DAY));
+        assert_eq!(
+            parse_expression_with("2d - 1h", &options).map_err(|e| e.error),
+            Err(Error::TooLong {
+                max: DAY,
+                value: Duration::new(169_200, 0)
+            })
+        );
+        assert_eq!(
+            parse_expression_with("2d - 1s", &options).map_err(|e| e.span),
+            Err(6..7)
+        );
+    }
+
+    #[test]
+    fn error_display() {
+        assert_eq!(
+            parse_bounded("500ms", SECOND, DAY).unwrap_err().to_string(),
+            "TooShortError: 500ms is shorter than the minimum of 1s"
+        );
+        assert_eq!(
+            parse_with("2 days", &Options::new().max(DAY))
+                .unwrap_err()
+                .to_string(),
+            "TooLongError: 172800s is longer than the maximum of 86400s"
+        );
+    }
+}
+
+mod limits {
+    use crate::options::{HARDENED_MAX_COMPONENTS, HARDENED_MAX_LENGTH};
+    use crate::parse::{Error, SpannedError};
+    use crate::{
+        parse_expression_with, parse_hardened, parse_range_with, parse_with, Options, Parser,
+    };
+    use ::std::time::Duration;
+
+    #[test]
+    fn within() {
+        let input = "1s".repeat(HARDENED_MAX_COMPONENTS);
+        assert_eq!(parse_hardened(&input), Ok(Duration::new(16, 0)));
+        let input = format!("{:>1$}", "1s", HARDENED_MAX_LENGTH);
+        assert_eq!(parse_hardened(&input), Ok(Duration::new(1, 0)));
+    }
+
+    #[test]
+    fn input_too_long() {
+        let input = format!("{:>1$}", "1s", HARDENED_MAX_LENGTH + 1);
+        assert_eq!(
+            parse_hardened(&input),
+            Err(Error::InputTooLong {
+                max: 256,
+                length: 257
+            })
+        );
+    }
+
+    #[test]
+    fn too_many_components() {
+        let input = "1s".repeat(HARDENED_MAX_COMPONENTS + 1);
+        assert_eq!(
+            parse_hardened(&input),
+            Err(Error::TooManyComponents { max: 16 })
+        );
+        // Checked before the rest of the input is read.
+        assert_eq!(
+            parse_with("1s 2s 3 blorp", &Options::new().max_components(2)),
+            Err(Error::TooManyComponents { max: 2 })
+        );
+    }
+
+    #[test]
+    fn unlimited_by_default() {
+        let input = "1s".repeat(10_000);
+        assert_eq!(
+            parse_with(&input, &Options::new()),
+            Ok(Duration::new(10_000, 0))
+        );
+    }
+
+    #[test]
+    fn with_other_options() {
+        let options = Options::hardened().natural_language(true);
+        assert_eq!(
+            parse_with("half an hour", &options),
+            Ok(Duration::new(1_800, 0))
+        );
+        let options = options.max_components(1);
+        assert_eq!(
+            parse_with("an hour and twenty minutes", &options),
+            Err(Error::TooManyComponents { max: 1 })
+        );
+        let mut parser = Parser::new(options);
+        assert_eq!(
+            parser.parse(&" ".repeat(300)),
+            Err(Error::InputTooLong {
+                max: 256,
+                length: 300
+            })
+        );
+    }
+
+    #[test]
+    fn expression() {
+        let options = Options::new().max_components(2).max_length(10);
+        assert_eq!(
+            parse_expression_with("2 * 5min", &options),
+            Ok(Duration::new(600, 0))
+        );
+        assert_eq!(
+            parse_expression_with("1h+2h+3h", &options),
+            Err(SpannedError {
+                error: Error::TooManyComponents { max: 2 },
+                span: 6..8,
+            })
+        );
+        assert_eq!(
+            parse_expression_with("1 hour + 2 hours", &options),
+            Err(SpannedError {
+                error: Error::InputTooLong {
+                    max: 10,
+                    length: 16
+                },
+                span: 0..16,
+            })
+        );
+    }
+
+    #[test]
+    fn range() {
+        let options = Options::new().max_components(1).max_length(12);
+        assert!(parse_range_with("5-10 minutes", &options).is_ok());
+        assert_eq!(
+            parse_range_with("1h1m - 2h", &options),
+            Err(Error::TooManyComponents { max: 1 })
+        );
+        assert_eq!(
+            parse_range_with("5 - 10 minutes", &options),
+            Err(Error::InputTooLong {
+                max: 12,
+                length: 14
+            })
+        );
+    }
+}
+
+mod calendar {
+    use crate::parse::Error;
+    use crate::{
+        parse_expression_with, parse_range_with, parse_with, Calendar, Options, Parser, Unit,
+    };
+    use ::std::ops::Bound::Included;
+    use ::std::time::Duration;
+
+    macro_rules! test_calendar {
+        (fn $fun:ident($calendar: expr, $string: expr, $seconds: expr, $nanoseconds: expr)) => {
+            #[test]
+            fn $fun() {
+                assert_eq!(
+                    parse_with($string, &Options::new().calendar($calendar)),
+                    Ok(Duration::new($seconds, $nanoseconds))
+                )
+            }
+        };
+    }
+
+    test_calendar!(fn gregorian_year(Calendar::Gregorian, "1 year", 31_556_952, 0));
+    test_calendar!(fn gregorian_month(Calendar::Gregorian, "1 month", 2_629_746, 0));
+    test_calendar!(fn julian_year(Calendar::Julian, "1 year", 31_557_600, 0));
+    test_calendar!(fn julian_month(Calendar::Julian, "1 month", 2_629_800, 0));
+    test_calendar!(fn julian_decimal(Calendar::Julian, "0.5 years", 15_778_800, 0));
+    test_calendar!(fn julian_century(Calendar::Julian, "1 century", 3_155_760_000, 0));
+    test_calendar!(fn fixed_year(Calendar::Fixed, "1 year", 31_536_000, 0));
+    test_calendar!(fn fixed_month(Calendar::Fixed, "1 month", 2_592_000, 0));
+    test_calendar!(fn fixed_decimal_month(Calendar::Fixed, "1.5 M", 3_888_000, 0));
+    test_calendar!(fn fixed_fraction(Calendar::Fixed, "1/3 month", 864_000, 0));
+    test_calendar!(fn fixed_quarter(Calendar::Fixed, "1 quarter", 7_776_000, 0));
+    test_calendar!(fn fixed_decade(Calendar::Fixed, "1 decade", 315_360_000, 0));
+    test_calendar!(fn fixed_mixed(Calendar::Fixed, "1y 1M 1d", 34_214_400, 0));
+    test_calendar!(fn fixed_negative(Calendar::Fixed, "1 year -0.5 months", 30_240_000, 0));
+    test_calendar!(fn reject_other_units(Calendar::Reject, "2 weeks 1 day", 1_296_000, 0));
+
+    #[test]
+    fn reject_lone_integer() {
+        let options = Options::new()
+            .calendar(Calendar::Reject)
+            .allowed_units(&[Unit::Day, Unit::Week]);
+        assert_eq!(
+            parse_with("15", &options),
+            Err(Error::UnitNotAllowed {
+                word: "15".to_owned(),
+                unit: Unit::Second
+            })
+        );
+    }
+
+    #[test]
+    fn default() {
+        assert_eq!(Calendar::default(), Calendar::Gregorian);
+        for input in ["1 year", "1.5 months", "3 quarters", "0.1 centuries"] {
+            let options = Options::new().calendar(Calendar::Gregorian);
+            assert_eq!(parse_with(input, &options), crate::parse(input));
+        }
+    }
+
+    #[test]
+    fn reject() {
+        let options = Options::new().calendar(Calendar::Reject);
+        for (input, word, unit) in [
+            ("1 month", "month", Unit::Month),
+            ("1.5 years", "years", Unit::Year),
+            ("1 day 1 qtr", "qtr", Unit::Quarter),
+            ("1 decade", "decade", Unit::Decade),
+            ("2 centuries", "centuries", Unit::Century),
+        ] {
+            let word = word.to_owned();
+            assert_eq!(
+                parse_with(input, &options),
+                Err(Error::UnitNotAllowed { word, unit })
+            );
+        }
+    }
+
+    #[test]
+    fn reject_with_allowed_units() {
+        let options = Options::new()
+            .calendar(Calendar::Reject)
+            .allowed_units(&[Unit::Day, Unit::Month]);
+        assert_eq!(
+            parse_with("2 days", &options),
+            Ok(Duration::new(172_800, 0))
+        );
+        assert!(parse_with("2 months", &options).is_err());
+    }
+
+    #[test]
+    fn natural_language() {
+        let options = Options::new()
+            .natural_language(true)
+            .calendar(Calendar::Fixed);
+        assert_eq!(
+            parse_with("a year and a half", &options),
+            Ok(Duration::new(47_304_000, 0))
+        );
+    }
+
+    #[test]
+    fn other_parsers() {
+        let options = Options::new().calendar(Calendar::Fixed);
+        let month = Duration::new(2_592_000, 0);
+        assert_eq!(Parser::new(options.clone()).parse("1 month"), Ok(month));
+        assert_eq!(
+            parse_expression_with("1 month / 2 + 0.5 months", &options),
+            Ok(month)
+        );
+        let range = parse_range_with("1-2 months", &options).unwrap();
+        assert_eq!(
+            (range.min, range.max),
+            (Included(month), Included(month * 2))
+        );
+    }
+
+    #[test]
+    fn overflow() {
+        let options = Options::new().calendar(Calendar::Julian);
+        assert_eq!(
+            parse_with("584554049254 years", &options),
+            Err(Error::Overflow)
+        );
+    }
+}
+
+mod case_policy {
+    use crate::parse::Error;
+    use crate::{locale, parse_expression_with, parse_with, CasePolicy, Options, Parser, Unit};
+    use ::std::time::Duration;
+
+    macro_rules! test_case {
+        (fn $fun:ident($policy: expr, $string: expr, $seconds: expr, $nanoseconds: expr)) => {
+            #[test]
+            fn $fun() {
+                assert_eq!(
+                    parse_with($string, &Options::new().case_policy($policy)),
+                    Ok(Duration::new($seconds, $nanoseconds))
+                )
+            }
+        };
+    }
+
+    macro_rules! test_case_invalid {
+        (fn $fun:ident($policy: expr, $string: expr, $error: expr)) => {
+            #[test]
+            fn $fun() {
+                assert_eq!(
+                    parse_with($string, &Options::new().case_policy($policy)),
+                    Err($error)
+                )
+            }
+        };
+    }
+
+    fn ambiguous(word: &str, candidates: &[Unit]) -> Error {
+        Error::AmbiguousUnit {
+            word: word.to_owned(),
+            candidates: candidates.to_vec(),
+        }
+    }
+
+    test_case!(fn mixed_minute(CasePolicy::Mixed, "5m", 300, 0));
+    test_case!(fn mixed_month(CasePolicy::Mixed, "1M", 2_629_746, 0));
+    test_case!(fn mixed_megasecond(CasePolicy::Mixed, "1Ms", 1_000_000, 0));
+
+    test_case!(fn insensitive_minute(CasePolicy::Insensitive, "5M", 300, 0));
+    test_case!(fn insensitive_millisecond(CasePolicy::Insensitive, "5Ms", 0, 5_000_000));
+    test_case!(fn insensitive_month(CasePolicy::Insensitive, "1 MONTH", 2_629_746, 0));
+    test_case!(fn insensitive_hours(CasePolicy::Insensitive, "1 Hours", 3_600, 0));
+
+    test_case!(fn sensitive_minute(CasePolicy::Sensitive, "5m", 300, 0));
+    test_case!(fn sensitive_month(CasePolicy::Sensitive, "1M", 2_629_746, 0));
+    test_case!(fn sensitive_megasecond(CasePolicy::Sensitive, "1Ms", 1_000_000, 0));
+    test_case!(fn sensitive_gigasecond(CasePolicy::Sensitive, "1Gs", 1_000_000_000, 0));
+    test_case!(fn sensitive_lower(CasePolicy::Sensitive, "1 hour 2 min", 3_720, 0));
+    test_case_invalid!(fn sensitive_upper(CasePolicy::Sensitive, "1 MIN", Error::UnknownUnit("MIN".to_owned())));
+    test_case_invalid!(fn sensitive_capital(CasePolicy::Sensitive, "1 Hour", Error::UnknownUnit("Hour".to_owned())));
+    test_case_invalid!(fn sensitive_month_prefix(CasePolicy::Sensitive, "1 Mon", Error::UnknownUnit("Mon".to_owned())));
+
+    test_case!(fn reject_minutes(CasePolicy::RejectAmbiguous, "5 min", 300, 0));
+    test_case!(fn reject_months(CasePolicy::RejectAmbiguous, "5 Mo", 13_148_730, 0));
+    test_case!(fn reject_milliseconds(CasePolicy::RejectAmbiguous, "5 msec", 0, 5_000_000));
+    test_case!(fn reject_seconds(CasePolicy::RejectAmbiguous, "5s", 5, 0));
+    test_case_invalid!(fn reject_lower_m(CasePolicy::RejectAmbiguous, "5m", ambiguous("m", &[Unit::Minute, Unit::Month])));
+    test_case_invalid!(fn reject_upper_m(CasePolicy::RejectAmbiguous, "5M", ambiguous("M", &[Unit::Minute, Unit::Month])));
+    test_case_invalid!(fn reject_ms(CasePolicy::RejectAmbiguous, "5ms", ambiguous("ms", &[Unit::Millisecond, Unit::Megasecond])));
+    test_case_invalid!(fn reject_upper_ms(CasePolicy::RejectAmbiguous, "5MS", ambiguous("MS", &[Unit::Millisecond, Unit::Megasecond])));
+    test_case_invalid!(fn reject_unknown(CasePolicy::RejectAmbiguous, "5 blorp", Error::UnknownUnit("blorp".to_owned())));
+
+    #[test]
+    fn default() {
+        assert_eq!(CasePolicy::default(), CasePolicy::Mixed);
+    }
+
+    #[test]
+    fn ambiguous_before_not_allowed() {
+        let options = Options::new()
+            .case_policy(CasePolicy::RejectAmbiguous)
+            .allowed_units(&[Unit::Second]);
+        assert_eq!(
+            parse_with("5m", &options),
+            Err(ambiguous("m", &[Unit::Minute, Unit::Month]))
+        );
+        assert_eq!(
+            parse_with("5 min", &options),
+            Err(Error::UnitNotAllowed {
+                word: "min".to_owned(),
+                unit: Unit::Minute
+            })
+        );
+    }
+
+    #[test]
+    fn locale_words() {
+        // Words of a locale are matched by the locale, whatever the policy.
+        let options = Options::new()
+            .locale(&locale::EN)
+            .case_policy(CasePolicy::RejectAmbiguous);
+        assert_eq!(parse_with("2 minutes", &options), Ok(Duration::new(120, 0)));
+    }
+
+    #[test]
+    fn other_parsers() {
+        let options = Options::new().case_policy(CasePolicy::RejectAmbiguous);
+        let error = ambiguous("m", &[Unit::Minute, Unit::Month]);
+        assert_eq!(Parser::new(options.clone()).parse("1m"), Err(error.clone()));
+        assert_eq!(
+            parse_expression_with("1h + 1m", &options).map_err(Error::from),
+            Err(error)
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            ambiguous("m", &[Unit::Minute, Unit::Month]).to_string(),
+            "AmbiguousUnitError: \"m\" could be any of [Minute, Month]"
+        );
+    }
+}
+
+mod spellings {
+    use crate::parse::Error;
+    use crate::{locale, parse_with, CasePolicy, Options, Spellings, Unit};
+    use ::std::time::Duration;
+
+    macro_rules! test_spellings {
+        (fn $fun:ident($spellings: expr, $string: expr, $seconds: expr, $nanoseconds: expr)) => {
+            #[test]
+            fn $fun() {
+                assert_eq!(
+                    parse_with($string, &Options::new().spellings($spellings)),
+                    Ok(Duration::new($seconds, $nanoseconds))
+                )
+            }
+        };
+    }
+
+    macro_rules! test_spellings_unknown {
+        (fn $fun:ident($spellings: expr, $string: expr, $word: expr)) => {
+            #[test]
+            fn $fun() {
+                assert_eq!(
+                    parse_with($string, &Options::new().spellings($spellings)),
+                    Err(Error::UnknownUnit($word.to_owned()))
+                )
+            }
+        };
+    }
+
+    test_spellings!(fn short(Spellings::canonical_short(), "1d 2h 3m 4s 5ms", 93_784, 5_000_000));
+    test_spellings!(fn short_micro(Spellings::canonical_short(), "1us 1 US", 0, 2_000));
+    test_spellings!(fn short_month(Spellings::canonical_short(), "1M", 2_629_746, 0));
+    test_spellings!(fn short_megasecond(Spellings::canonical_short(), "1Ms", 1_000_000, 0));
+    test_spellings!(fn short_upper_case(Spellings::canonical_short(), "1H 1MS", 3_600, 1_000_000));
+    test_spellings_unknown!(fn short_prefix(Spellings::canonical_short(), "1 min", "min"));
+    test_spellings_unknown!(fn short_long(Spellings::canonical_short(), "1 hour", "hour"));
+    test_spellings_unknown!(fn short_fortnight(Spellings::canonical_short(), "1 fortnight", "fortnight"));
+
+    test_spellings!(fn long(Spellings::canonical_long(), "1 day 2 hours 1 minute", 93_660, 0));
+    test_spellings!(fn long_new_units(Spellings::canonical_long(), "1 fortnight 2 jiffies", 1_209_600, 20_000_000));
+    test_spellings!(fn long_upper_case(Spellings::canonical_long(), "1 HOUR", 3_600, 0));
+    test_spellings_unknown!(fn long_mill(Spellings::canonical_long(), "1 mill", "mill"));
+    test_spellings_unknown!(fn long_mic(Spellings::canonical_long(), "1 mic", "mic"));
+    test_spellings_unknown!(fn long_hou(Spellings::canonical_long(), "1 hou", "hou"));
+    test_spellings_unknown!(fn long_wee(Spellings::canonical_long(), "1 wee", "wee"));
+    test_spellings_unknown!(fn long_yea(Spellings::canonical_long(), "1 yea", "yea"));
+    test_spellings_unknown!(fn long_symbol(Spellings::canonical_long(), "1h", "h"));
+
+    test_spellings!(fn systemd(Spellings::systemd(), "2 hr 5 sec 3 usec", 7_205, 3_000));
+    test_spellings!(fn systemd_month(Spellings::systemd(), "1 M 1 m", 2_629_806, 0));
+    test_spellings_unknown!(fn systemd_mins(Spellings::systemd(), "1 mins", "mins"));
+    test_spellings_unknown!(fn systemd_hrs(Spellings::systemd(), "1 hrs", "hrs"));
+
+    test_spellings!(fn custom(Spellings::new().unit(Unit::Minute, &["min", "mins"]), "2 mins", 120, 0));
+    test_spellings!(fn extend_preset(Spellings::canonical_short().unit(Unit::Day, &["days"]), "2 days 1h", 176_400, 0));
+    test_spellings_unknown!(fn empty(Spellings::new(), "1s", "s"));
+
+    #[test]
+    fn case_policies() {
+        let short = || Options::new().spellings(Spellings::canonical_short());
+        let sensitive = short().case_policy(CasePolicy::Sensitive);
+        assert_eq!(parse_with("1h", &sensitive), Ok(Duration::new(3_600, 0)));
+        assert_eq!(
+            parse_with("1H", &sensitive),
+            Err(Error::UnknownUnit("H".to_owned()))
+        );
+        let insensitive = short().case_policy(CasePolicy::Insensitive);
+        assert_eq!(
+            parse_with("1M", &insensitive),
+            Ok(Duration::new(2_629_746, 0))
+        );
+        assert_eq!(parse_with("1m", &insensitive), Ok(Duration::new(60, 0)));
+        let mixed = Options::new().spellings(Spellings::new().unit(Unit::Month, &["M"]));
+        assert_eq!(
+            parse_with("1m", &mixed),
+            Err(Error::UnknownUnit("m".to_owned()))
+        );
+    }
+
+    #[test]
+    fn ambiguous() {
+        let options = Options::new()
+            .spellings(Spellings::canonical_short())
+            .case_policy(CasePolicy::RejectAmbiguous);
+        assert_eq!(parse_with("1h", &options), Ok(Duration::new(3_600, 0)));
+        assert_eq!(
+            parse_with("1M", &options),
+            Err(Error::AmbiguousUnit {
+                word: "M".to_owned(),
+                candidates: vec![Unit::Minute, Unit::Month],
+            })
+        );
+        assert_eq!(
+            parse_with("1ms", &options),
+            Err(Error::AmbiguousUnit {
+                word: "ms".to_owned(),
+                candidates: vec![Unit::Millisecond, Unit::Megasecond],
+            })
+        );
+        // Without a spelling for months, "m" is not ambiguous.
+        let options = Options::new()
+            .spellings(Spellings::new().unit(Unit::Minute, &["m"]))
+            .case_policy(CasePolicy::RejectAmbiguous);
+        assert_eq!(parse_with("1M", &options), Ok(Duration::new(60, 0)));
+    }
+
+    #[test]
+    fn not_allowed() {
+        let options = Options::new()
+            .spellings(Spellings::canonical_short())
+            .allowed_units(&[Unit::Second]);
+        assert_eq!(
+            parse_with("1h", &options),
+            Err(Error::UnitNotAllowed {
+                word: "h".to_owned(),
+                unit: Unit::Hour
+            })
+        );
+    }
+
+    #[test]
+    fn locale_words() {
+        let options = Options::new()
+            .locale(&locale::EN)
+            .spellings(Spellings::new());
+        assert_eq!(parse_with("2 minutes", &options), Ok(Duration::new(120, 0)));
+    }
+
+    #[test]
+    fn natural_language() {
+        let options = Options::new()
+            .natural_language(true)
+            .spellings(Spellings::canonical_long());
+        assert_eq!(
+            parse_with("an hour and a half", &options),
+            Ok(Duration::new(5_400, 0))
+        );
+    }
+}
+
+mod find {
+    use crate::find_durations;
+    use ::std::time::Duration;
+
+    macro_rules! test_find {
+        (fn $fun:ident($text: expr, [$(($found: expr, $seconds: expr, $nanoseconds: expr)),*])) => {
+            #[test]
+            fn $fun() {
+                let text = $text;
+                let found: Vec<_> = find_durations(text)
+                    .map(|found| (&text[found.span], found.duration))
+                    .collect();
+                let expected: Vec<(&str, Duration)> =
+                    vec![$(($found, Duration::new($seconds, $nanoseconds))),*];
+                assert_eq!(found, expected);
+            }
+        };
+    }
+
+    test_find!(fn empty("", []));
+    test_find!(fn no_durations("ticket 1234 was closed", []));
+    test_find!(fn single("it took 3 hours", [("3 hours", 10_800, 0)]));
+    test_find!(fn commas("took 3.5s, retried after 200ms, timed out at 30 s", [
+        ("3.5s", 3, 500_000_000),
+        ("200ms", 0, 200_000_000),
+        ("30 s", 30, 0)
+    ]));
+    test_find!(fn summed("waited 1 hour 15 minutes and 29 seconds", [("1 hour 15 minutes and 29 seconds", 4_529, 0)]));
+    test_find!(fn adjacent("1h30m", [("1h30m", 5_400, 0)]));
+    test_find!(fn sentences("It took 5 min. 10 seconds later it failed", [
+        ("5 min", 300, 0),
+        ("10 seconds", 10, 0)
+    ]));
+    test_find!(fn other_words("2 days ago, or 3 hours from now", [("2 days", 172_800, 0), ("3 hours", 10_800, 0)]));
+    test_find!(fn word_between("1 hour then 5 minutes", [("1 hour", 3_600, 0), ("5 minutes", 300, 0)]));
+    test_find!(fn number_without_unit("1 hour 5 apples 2 s", [("1 hour", 3_600, 0), ("2 s", 2, 0)]));
+    test_find!(fn trailing_and("1 hour and", [("1 hour", 3_600, 0)]));
+    test_find!(fn negative("1 day -1 hour", [("1 day -1 hour", 82_800, 0)]));
+    test_find!(fn range("3s-5s", [("3s", 3, 0), ("5s", 5, 0)]));
+    test_find!(fn spaced_minus("1 day - 2 hours", [("1 day", 86_400, 0), ("2 hours", 7_200, 0)]));
+    test_find!(fn fractions("½ h or 1/4 day", [("½ h", 1_800, 0), ("1/4 day", 21_600, 0)]));
+    test_find!(fn invalid_skipped("1e5 s, -1 h, 2 s", [("2 s", 2, 0)]));
+    test_find!(fn overflow_skipped("9999999999999999 years; 1 week", [("1 week", 604_800, 0)]));
+    test_find!(fn unicode("durée: 5 min — 10 s", [("5 min", 300, 0), ("10 s", 10, 0)]));
+
+    #[test]
+    fn spans() {
+        let text = "a 1m b";
+        let found: Vec<_> = find_durations(text).collect();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].span, 2..4);
+    }
+}
+
+mod lines {
+    use crate::parse::Error;
+    use crate::{Options, Parser};
+    use ::std::io::{self, BufReader, Read};
+    use ::std::time::Duration;
+
+    fn parse_lines(input: &[u8]) -> Vec<io::Result<(usize, Result<Duration, Error>)>> {
+        Parser::default().parse_lines(input).collect()
+    }
+
+    fn parse_column(input: &str, delimiter: char, index: usize) -> Vec<Result<Duration, Error>> {
+        Parser::default()
+            .parse_lines(input.as_bytes())
+            .column(delimiter, index)
+            .map(|line| line.unwrap().1)
+            .collect()
+    }
+
+    fn seconds(seconds: u64) -> Result<Duration, Error> {
+        Ok(Duration::new(seconds, 0))
+    }
+
+    #[test]
+    fn line_numbers() {
+        let lines: Vec<_> = parse_lines(b"1s\n2s\n3 foo\n")
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                (1, seconds(1)),
+                (2, seconds(2)),
+                (3, Err(Error::UnknownUnit("foo".to_owned())))
+            ]
+        );
+    }
+
+    #[test]
+    fn line_endings() {
+        let lines: Vec<_> = parse_lines(b"1s\r\n2s\n\r\n3s")
+            .into_iter()
+            .map(|line| line.unwrap().1)
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                seconds(1),
+                seconds(2),
+                Err(Error::NoValueFound(String::new())),
+                seconds(3)
+            ]
+        );
+    }
+
+    #[test]
+    fn empty() {
+        assert!(parse_lines(b"").is_empty());
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let lines = parse_lines(b"1s\n\xff s\n3s\n");
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].as_ref().unwrap(), &(1, seconds(1)));
+        assert_eq!(
+            lines[1].as_ref().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert_eq!(lines[2].as_ref().unwrap(), &(3, seconds(3)));
+    }
+
+    #[test]
+    fn read_error() {
+        struct Failing;
+        impl Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("disk on fire"))
+            }
+        }
+        let mut parser = Parser::default();
+        let mut lines = parser.parse_lines(BufReader::new(Failing));
+        assert_eq!(
+            lines.next().unwrap().unwrap_err().to_string(),
+            "disk on fire"
+        );
+    }
+
+    #[test]
+    fn options() {
+        let mut parser = Parser::new(Options::new().strict(true));
+        let lines: Vec<_> = parser
+            .parse_lines(&b"1h 30m\n1h, 30m"[..])
+            .map(|line| line.unwrap().1)
+            .collect();
+        assert_eq!(
+            lines,
+            vec![seconds(5_400), Err(Error::UnexpectedToken(",".to_owned()))]
+        );
+    }
+
+    #[test]
+    fn buffered_reader() {
+        // Lines span the buffer of the reader.
+        let input = "1 second\n".repeat(1_000);
+        let reader = BufReader::with_capacity(7, input.as_bytes());
+        let mut parser = Parser::default();
+        let lines = parser.parse_lines(reader);
+        assert_eq!(
+            lines.filter(|line| matches!(line, Ok((_, Ok(_))))).count(),
+            1_000
+        );
+    }
+
+    #[test]
+    fn column() {
+        assert_eq!(
+            parse_column("a,1h,b\nc,2 s,d", ',', 1),
+            vec![seconds(3_600), seconds(2)]
+        );
+        assert_eq!(parse_column("1h,b", ',', 0), vec![seconds(3_600)]);
+        assert_eq!(parse_column("a,b,3s", ',', 2), vec![seconds(3)]);
+    }
+
+    #[test]
+    fn column_quoted() {
+        assert_eq!(
+            parse_column("build,\"1h, 30m\",ok\ntest, \"45s\" ", ',', 1),
+            vec![seconds(5_400), seconds(45)]
+        );
+    }
+
+    #[test]
+    fn column_delimiters() {
+        assert_eq!(parse_column("a\t1h\tb", '\t', 1), vec![seconds(3_600)]);
+        assert_eq!(parse_column("a;1.5h", ';', 1), vec![seconds(5_400)]);
+        assert_eq!(parse_column("a→1h", '→', 1), vec![seconds(3_600)]);
+    }
+
+    #[test]
+    fn column_missing() {
+        assert_eq!(
+            parse_column("a,1h", ',', 2),
+            vec![Err(Error::NoValueFound("a,1h".to_owned()))]
+        );
+        assert_eq!(
+            parse_column("a,", ',', 1),
+            vec![Err(Error::NoValueFound(String::new()))]
+        );
+    }
+}
+
+mod strict {
+    use crate::parse::Error;
+    use crate::{parse_with, Options};
+    use ::std::time::Duration;
+
+    fn strict(input: &str) -> Result<Duration, Error> {
+        parse_with(input, &Options::new().strict(true))
+    }
+
+    #[test]
+    fn accepts_components() {
+        assert_eq!(strict("1h 30m"), Ok(Duration::new(5_400, 0)));
+        assert_eq!(strict("  1h30m\t5 s "), Ok(Duration::new(5_405, 0)));
+        assert_eq!(strict("1 day -1 hour"), Ok(Duration::new(82_800, 0)));
+    }
+
+    #[test]
+    fn rejects_junk() {
+        assert_eq!(
+            strict("1h, 30m"),
+            Err(Error::UnexpectedToken(",".to_owned()))
+        );
+        assert_eq!(
+            strict("Duration: 1h"),
+            Err(Error::UnexpectedToken("Duration".to_owned()))
+        );
+        assert_eq!(
+            strict("1h and 30m"),
+            Err(Error::UnexpectedToken("and".to_owned()))
+        );
+    }
+
+    #[test]
+    fn rejects_lone_number() {
+        assert_eq!(strict("15"), Err(Error::NoUnitFound("15".to_owned())));
+    }
+
+    #[test]
+    fn natural_language() {
+        let options = Options::new().strict(true).natural_language(true);
+        assert_eq!(
+            parse_with("twenty minutes", &options),
+            Ok(Duration::new(1_200, 0))
+        );
+        assert_eq!(
+            parse_with("in twenty minutes", &options),
+            Err(Error::UnexpectedToken("in".to_owned()))
+        );
+    }
+}
+
+mod iso8601 {
+    use crate::format_iso8601;
+    use ::std::time::Duration;
+
+    #[test]
+    fn format() {
+        assert_eq!(format_iso8601(Duration::new(0, 0)), "PT0S");
+        assert_eq!(format_iso8601(Duration::new(86_400, 0)), "P1D");
+        assert_eq!(format_iso8601(Duration::new(90_061, 0)), "P1DT1H1M1S");
+        assert_eq!(format_iso8601(Duration::new(3_600, 0)), "PT1H");
+        assert_eq!(format_iso8601(Duration::new(0, 1)), "PT0.000000001S");
+        assert_eq!(
+            format_iso8601(Duration::new(86_400, 500_000_000)),
+            "P1DT0.5S"
+        );
+    }
+}
+
+#[cfg(feature = "ffi")]
+mod ffi {
+    use crate::ffi::{pd_last_error, pd_parse, PdStatus};
+    use ::std::ffi::CStr;
+    use ::std::ptr;
+
+    fn parse(input: &str) -> (PdStatus, u64, u32) {
+        let (mut secs, mut nanos) = (0, 0);
+        let status = unsafe { pd_parse(input.as_ptr().cast(), input.len(), &mut secs, &mut nanos) };
+        (status, secs, nanos)
+    }
+
+    fn last_error() -> (PdStatus, Option<String>, usize, usize) {
+        let (mut message, mut start, mut end) = (ptr::null(), 0, 0);
+        let status = unsafe { pd_last_error(&mut message, &mut start, &mut end) };
+        let message = (!message.is_null()).then(|| {
+            unsafe { CStr::from_ptr(message) }
+                .to_string_lossy()
+                .into_owned()
+        });
+        (status, message, start, end)
+    }
+
+    #[test]
+    fn valid() {
+        assert_eq!(parse("1h 0.5s"), (PdStatus::Ok, 3_600, 500_000_000));
+        assert_eq!(last_error(), (PdStatus::Ok, None, 0, 0));
+    }
+
+    #[test]
+    fn unknown_unit_span() {
+        assert_eq!(parse("5 min 3 m 2 x").0, PdStatus::UnknownUnit);
+        assert_eq!(
+            last_error(),
+            (
+                PdStatus::UnknownUnit,
+                Some("UnknownUnitError: \"x\" is not a known unit".to_owned()),
+                12,
+                13
+            )
+        );
+    }
+
+    #[test]
+    fn error_codes() {
+        assert_eq!(parse("1 day -2 days").0, PdStatus::OutOfBounds);
+        assert_eq!(parse("1e5s").0, PdStatus::ExpNotSupported);
+        assert_eq!(parse("1/0 h").0, PdStatus::DivisionByZero);
+        assert_eq!(last_error().2..last_error().3, 0..3);
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let (mut secs, mut nanos) = (0, 0);
+        let input = b"1 \xff s";
+        let status = unsafe { pd_parse(input.as_ptr().cast(), input.len(), &mut secs, &mut nanos) };
+        assert_eq!(status, PdStatus::InvalidUtf8);
+        assert_eq!(last_error().2..last_error().3, 2..3);
+    }
+
+    #[test]
+    fn null_pointers() {
+        let status = unsafe { pd_parse(ptr::null(), 0, &mut 0, &mut 0) };
+        assert_eq!(status, PdStatus::NullPointer);
+        assert_eq!(last_error().0, PdStatus::NullPointer);
+    }
+}
+
+#[cfg(feature = "wasm")]
+mod wasm {
+    use crate::wasm::utf16_span;
+
+    #[test]
+    fn span_in_utf16() {
+        assert_eq!(utf16_span("1 h 2 x", 6..7), 6..7);
+        // 'é' is two bytes and one code unit, '𝄞' is four bytes and two code units.
+        assert_eq!(utf16_span("é 𝄞 2 x", 9..10), 6..7);
+        assert_eq!(utf16_span("2 𝄞", 2..6), 2..4);
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    mod node {
+        use crate::wasm::{format_nanoseconds, parse_milliseconds, parse_nanoseconds};
+        use ::js_sys::{Error, Reflect};
+        use ::wasm_bindgen::{JsCast, JsValue};
+        use ::wasm_bindgen_test::wasm_bindgen_test;
+
+        fn property(value: &JsValue, key: &str) -> JsValue {
+            Reflect::get(value, &JsValue::from_str(key)).unwrap()
+        }
+
+        #[wasm_bindgen_test]
+        fn parse() {
+            assert_eq!(parse_nanoseconds("1h 0.5s").unwrap(), 3_600_500_000_000);
+            assert_eq!(parse_milliseconds("1.5 us 2s").unwrap(), 2_000);
+        }
+
+        #[wasm_bindgen_test]
+        fn error() {
+            let error = parse_nanoseconds("é 2 blorps").unwrap_err();
+            let message = error.dyn_ref::<Error>().unwrap().message();
+            assert_eq!(message, "UnknownUnitError: \"blorps\" is not a known unit");
+            assert_eq!(property(&error, "name"), "ParseDurationError");
+            assert_eq!(property(&error, "kind"), "UnknownUnit");
+            assert_eq!(property(&error, "start").as_f64(), Some(4.0));
+            assert_eq!(property(&error, "end").as_f64(), Some(10.0));
+        }
+
+        #[wasm_bindgen_test]
+        fn overflow() {
+            let error = parse_nanoseconds("1000 years").unwrap_err();
+            assert_eq!(property(&error, "kind"), "Overflow");
+        }
+
+        #[wasm_bindgen_test]
+        fn format() {
+            assert_eq!(
+                format_nanoseconds(4_529_000_000_000),
+                "1 hour 15 minutes and 29 seconds"
+            );
+        }
+    }
+}
+
+#[cfg(feature = "python")]
+mod python {
+    use crate::python::parse_duration;
+    use ::pyo3::prelude::*;
+    use ::pyo3::types::{PyDict, PyModule};
+    use ::std::ffi::CStr;
+
+    /// Run Python code with the module imported as `pd`.
+    fn run(code: &CStr) {
+        Python::initialize();
+        Python::attach(|py| -> PyResult<()> {
+            let module = PyModule::new(py, "parse_duration")?;
+            parse_duration(&module)?;
+            let globals = PyDict::new(py);
+            globals.set_item("pd", module)?;
+            py.run(code, Some(&globals), None)
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn parse() {
+        run(c"
+from datetime import timedelta
+assert pd.parse('1 hour 15 minutes') == timedelta(hours=1, minutes=15)
+assert pd.parse('1.5 us 2ns') == timedelta(microseconds=1)
+");
+    }
+
+    #[test]
+    fn format() {
+        run(c"
+from datetime import timedelta
+assert pd.format(timedelta(seconds=4529)) == '1 hour 15 minutes and 29 seconds'
+try:
+    pd.format(timedelta(seconds=-1))
+    assert False
+except ValueError:
+    pass
+");
+    }
+
+    #[test]
+    fn errors() {
+        run(c"
+try:
+    pd.parse('1 blorp')
+    assert False
+except pd.UnknownUnitError as error:
+    assert isinstance(error, pd.ParseDurationError)
+    assert isinstance(error, ValueError)
+    assert str(error) == 'UnknownUnitError: \"blorp\" is not a known unit'
+
+for text, error in [('1e5 s', pd.ExpNotSupportedError), ('', pd.NoValueFoundError),
+                    ('1 day -2 days', pd.OutOfBoundsError), ('1/0 h', pd.DivisionByZeroError)]:
+    try:
+        pd.parse(text)
+        assert False, text
+    except error:
+        pass
+");
+    }
+
+    #[test]
+    fn out_of_range() {
+        run(c"
+from datetime import timedelta
+assert pd.parse('999999999 days') == timedelta.max - timedelta(seconds=86399, microseconds=999999)
+try:
+    pd.parse('1000000000 days')
+    assert False
+except pd.DurationOverflowError:
+    pass
+assert not hasattr(pd, 'OverflowError')
+");
+    }
+}
+
+mod prometheus {
+    use crate::parse::Error;
+    use crate::{format_prometheus, parse_prometheus};
+    use ::std::time::Duration;
+
+    macro_rules! test_prometheus {
+        (fn $fun:ident($string: expr, $seconds: expr, $nanoseconds: expr)) => {
+            test_prometheus!(fn $fun($string, $seconds, $nanoseconds, $string));
+        };
+        (fn $fun:ident($string: expr, $seconds: expr, $nanoseconds: expr, $formatted: expr)) => {
+            #[test]
+            fn $fun() {
+                let duration = Duration::new($seconds, $nanoseconds);
+                assert_eq!(parse_prometheus($string), Ok(duration));
+                assert_eq!(format_prometheus(duration), $formatted);
+            }
+        };
+    }
+
+    macro_rules! test_prometheus_invalid {
+        (fn $fun:ident($string: expr, $error: expr)) => {
+            #[test]
+            fn $fun() {
+                assert_eq!(parse_prometheus($string), Err($error));
+            }
+        };
+    }
+
+    fn invalid(input: &str) -> Error {
+        Error::InvalidFormat(input.to_owned())
+    }
+
+    // The cases of TestParseDuration in prometheus/common.
+    test_prometheus!(fn zero("0s", 0, 0));
+    test_prometheus!(fn seconds("324ms", 0, 324_000_000));
+    test_prometheus!(fn three_seconds("3s", 3, 0));
+    test_prometheus!(fn minutes("5m", 300, 0));
+    test_prometheus!(fn hours("1h", 3_600, 0));
+    test_prometheus!(fn days("4d", 345_600, 0));
+    test_prometheus!(fn days_hours("4d1h", 349_200, 0));
+    test_prometheus!(fn ninety_days("90d", 7_776_000, 0));
+    test_prometheus!(fn weeks("3w", 1_814_400, 0));
+    test_prometheus!(fn weeks_days("3w2d1h", 1_990_800, 0, "23d1h"));
+    test_prometheus!(fn years_weeks("1y1w1d", 32_227_200, 0, "373d"));
+    test_prometheus!(fn years("10y", 315_360_000, 0));
+    test_prometheus!(fn all_units("1y2d3h4m5s6ms", 31_719_845, 6_000_000, "367d3h4m5s6ms"));
+
+    #[test]
+    fn zero_without_unit() {
+        assert_eq!(parse_prometheus("0"), Ok(Duration::new(0, 0)));
+    }
+
+    #[test]
+    fn not_normalized() {
+        assert_eq!(parse_prometheus("90m"), Ok(Duration::new(5_400, 0)));
+        assert_eq!(parse_prometheus("0d1h"), Ok(Duration::new(3_600, 0)));
+        assert_eq!(parse_prometheus("14d"), Ok(Duration::new(1_209_600, 0)));
+        assert_eq!(format_prometheus(Duration::new(1_209_600, 0)), "2w");
+    }
+
+    #[test]
+    fn format_rounds_down() {
+        assert_eq!(format_prometheus(Duration::new(0, 999_999)), "0s");
+        assert_eq!(format_prometheus(Duration::new(1, 1_999_999)), "1s1ms");
+    }
+
+    test_prometheus_invalid!(fn empty("", Error::NoValueFound(String::new())));
+    test_prometheus_invalid!(fn no_unit("1", invalid("1")));
+    test_prometheus_invalid!(fn trailing_number("1h30", invalid("1h30")));
+    test_prometheus_invalid!(fn decimal("1.5h", Error::UnknownUnit(".".to_owned())));
+    test_prometheus_invalid!(fn negative("-1h", invalid("-1h")));
+    test_prometheus_invalid!(fn space("1h 30m", Error::UnknownUnit("h ".to_owned())));
+    test_prometheus_invalid!(fn leading_space(" 1h", invalid(" 1h")));
+    test_prometheus_invalid!(fn wrong_order("30m1h", invalid("30m1h")));
+    test_prometheus_invalid!(fn repeated_unit("1h1h", invalid("1h1h")));
+    test_prometheus_invalid!(fn long_unit("1hour", Error::UnknownUnit("hour".to_owned())));
+    test_prometheus_invalid!(fn microseconds("1us", Error::UnknownUnit("us".to_owned())));
+    test_prometheus_invalid!(fn upper_case("1H", Error::UnknownUnit("H".to_owned())));
+    test_prometheus_invalid!(fn unit_only("h", invalid("h")));
+    test_prometheus_invalid!(fn too_many_digits("99999999999999999999s", invalid("99999999999999999999s")));
+    test_prometheus_invalid!(fn too_long("293y", Error::Overflow));
+    test_prometheus_invalid!(fn sum_too_long("292y25w", Error::Overflow));
+
+    #[test]
+    fn longest() {
+        assert_eq!(
+            parse_prometheus("292y24w3d23h47m16s854ms"),
+            Ok(Duration::new(9_223_372_036, 854_000_000))
+        );
+    }
+}
+
+mod go {
+    use crate::parse::Error;
+    use crate::{lint_go, parse_go, parse_go_with, GoOptions, SpannedError, Unit};
+    use ::std::time::Duration;
+
+    macro_rules! test_go {
+        (fn $fun:ident($string: expr, $seconds: expr, $nanoseconds: expr)) => {
+            #[test]
+            fn $fun() {
+                assert_eq!(parse_go($string), Ok(Duration::new($seconds, $nanoseconds)));
+                assert_eq!(lint_go($string, &GoOptions::new()), vec![]);
+            }
+        };
+    }
+
+    macro_rules! test_go_invalid {
+        (fn $fun:ident($string: expr, $error: expr)) => {
+            #[test]
+            fn $fun() {
+                assert_eq!(parse_go($string), Err($error));
+            }
+        };
+    }
+
+    fn invalid(input: &str) -> Error {
+        Error::InvalidFormat(input.to_owned())
+    }
+
+    fn kubernetes() -> GoOptions {
+        GoOptions::new()
+            .extension("d", Unit::Day)
+            .extension("w", Unit::Week)
+    }
+
+    // The valid cases of TestParseDuration in Go, without the negative ones.
+    test_go!(fn zero("0", 0, 0));
+    test_go!(fn seconds("5s", 5, 0));
+    test_go!(fn many_seconds("30s", 30, 0));
+    test_go!(fn large_seconds("1478s", 1_478, 0));
+    test_go!(fn plus("+5s", 5, 0));
+    test_go!(fn negative_zero("-0", 0, 0));
+    test_go!(fn plus_zero("+0", 0, 0));
+    test_go!(fn decimal("5.0s", 5, 0));
+    test_go!(fn decimal_half("5.6s", 5, 600_000_000));
+    test_go!(fn trailing_dot("5.s", 5, 0));
+    test_go!(fn leading_dot(".5s", 0, 500_000_000));
+    test_go!(fn one_decimal("1.0s", 1, 0));
+    test_go!(fn long_decimal("1.004s", 1, 4_000_000));
+    test_go!(fn small_decimal("1.0040s", 1, 4_000_000));
+    test_go!(fn padded_decimal("100.00100s", 100, 1_000_000));
+    test_go!(fn nanoseconds("10ns", 0, 10));
+    test_go!(fn microseconds("11us", 0, 11_000));
+    test_go!(fn micro_sign("12\u{b5}s", 0, 12_000));
+    test_go!(fn greek_mu("12\u{3bc}s", 0, 12_000));
+    test_go!(fn milliseconds("13ms", 0, 13_000_000));
+    test_go!(fn minutes("15m", 900, 0));
+    test_go!(fn hours("16h", 57_600, 0));
+    test_go!(fn hours_minutes("3h30m", 12_600, 0));
+    test_go!(fn mixed("10.5s4m", 250, 500_000_000));
+    test_go!(fn all_units("1h2m3s4ms5us6ns", 3_723, 4_005_006));
+    test_go!(fn fractional_seconds("39h9m14.425s", 140_954, 425_000_000));
+    test_go!(fn fraction_of_hour("0.25h", 900, 0));
+    test_go!(fn many_digits("1.00000000000000000000000000000000000000000001s", 1, 0));
+    test_go!(fn largest("9223372036854775807ns", 9_223_372_036, 854_775_807));
+    test_go!(fn largest_fraction("9223372036s854ms775us807ns", 9_223_372_036, 854_775_807));
+    test_go!(fn any_order("1s1h1s", 3_602, 0));
+
+    test_go_invalid!(fn empty("", Error::NoValueFound(String::new())));
+    test_go_invalid!(fn sign_only("-", invalid("-")));
+    test_go_invalid!(fn no_unit("3", Error::NoUnitFound("3".to_owned())));
+    test_go_invalid!(fn trailing_number("1h30", Error::NoUnitFound("30".to_owned())));
+    test_go_invalid!(fn unit_only("s", invalid("s")));
+    test_go_invalid!(fn dot("1.2.3s", invalid("1.2.3s")));
+    test_go_invalid!(fn dot_only(".s", invalid(".s")));
+    test_go_invalid!(fn space("1h 30m", invalid("1h 30m")));
+    test_go_invalid!(fn second_sign("1h-30m", invalid("1h-30m")));
+    test_go_invalid!(fn double_sign("--1h", invalid("--1h")));
+    test_go_invalid!(fn fraction("1/2h", invalid("1/2h")));
+    test_go_invalid!(fn hours_too_long("3000000h0s", Error::Overflow));
+    test_go_invalid!(fn day("1d", Error::UnknownUnit("d".to_owned())));
+    test_go_invalid!(fn long_unit("1hour", Error::UnknownUnit("hour".to_owned())));
+    test_go_invalid!(fn upper_case("1H", Error::UnknownUnit("H".to_owned())));
+    test_go_invalid!(fn too_long("9223372036854775808ns", Error::Overflow));
+    test_go_invalid!(fn sum_too_long("9223372036854775807ns1ns", Error::Overflow));
+    test_go_invalid!(fn too_many_digits("99999999999999999999999999999999999999999s", Error::Overflow));
+    test_go_invalid!(fn negative("-1h", Error::OutOfBounds(-3_600)));
+
+    #[test]
+    fn extensions() {
+        assert_eq!(
+            parse_go_with("1d12h", &kubernetes()),
+            Ok(Duration::new(129_600, 0))
+        );
+        assert_eq!(
+            parse_go_with("1.5w", &kubernetes()),
+            Ok(Duration::new(907_200, 0))
+        );
+        assert_eq!(
+            parse_go_with("1y", &kubernetes()),
+            Err(Error::UnknownUnit("y".to_owned()))
+        );
+        // The units of Go take precedence.
+        let options = GoOptions::new().extension("m", Unit::Month);
+        assert_eq!(parse_go_with("1m", &options), Ok(Duration::new(60, 0)));
+    }
+
+    #[test]
+    fn lint() {
+        assert_eq!(lint_go("1h30m", &kubernetes()), vec![]);
+        assert_eq!(
+            lint_go("2w1d12h", &kubernetes()),
+            vec![
+                SpannedError::new(Error::UnknownUnit("w".to_owned()), 1..2),
+                SpannedError::new(Error::UnknownUnit("d".to_owned()), 3..4),
+            ]
+        );
+        assert_eq!(
+            lint_go("1h 30m", &kubernetes()),
+            vec![SpannedError::new(invalid("1h 30m"), 2..3)]
+        );
+        assert_eq!(
+            lint_go("1d 1y", &kubernetes()),
+            vec![SpannedError::new(invalid("1d 1y"), 2..3)]
+        );
+        assert_eq!(
+            lint_go("1h1y", &kubernetes()),
+            vec![SpannedError::new(Error::UnknownUnit("y".to_owned()), 3..4)]
+        );
+        // Go accepts negative durations.
+        assert_eq!(lint_go("-1h", &GoOptions::new()), vec![]);
+    }
+}
+
+mod java {
+    use crate::parse::Error;
+    use crate::{format_java, parse_java};
+    use ::std::time::Duration;
+
+    macro_rules! test_java {
+        (fn $fun:ident($string: expr, $seconds: expr, $nanoseconds: expr)) => {
+            #[test]
+            fn $fun() {
+                assert_eq!(
+                    parse_java($string),
+                    Ok(Duration::new($seconds, $nanoseconds))
+                );
+            }
+        };
+    }
+
+    macro_rules! test_java_invalid {
+        (fn $fun:ident($string: expr, $error: expr)) => {
+            #[test]
+            fn $fun() {
+                assert_eq!(parse_java($string), Err($error));
+            }
+        };
+    }
+
+    fn invalid(input: &str) -> Error {
+        Error::InvalidFormat(input.to_owned())
+    }
+
+    // The examples of Duration.parse in the Java documentation.
+    test_java!(fn seconds("PT20.345S", 20, 345_000_000));
+    test_java!(fn minutes("PT15M", 900, 0));
+    test_java!(fn hours("PT10H", 36_000, 0));
+    test_java!(fn days("P2D", 172_800, 0));
+    test_java!(fn days_hours_minutes("P2DT3H4M", 183_840, 0));
+    test_java!(fn negative_component_negated("-PT-6H+3M", 21_420, 0));
+    test_java_invalid!(fn negative_component("PT-6H3M", Error::OutOfBounds(-21_420)));
+    test_java_invalid!(fn negative("-PT6H3M", Error::OutOfBounds(-21_780)));
+
+    test_java!(fn zero("PT0S", 0, 0));
+    test_java!(fn lower_case("p1dt2h3m4s", 93_784, 0));
+    test_java!(fn comma("PT1,5S", 1, 500_000_000));
+    test_java!(fn trailing_dot("PT1.S", 1, 0));
+    test_java!(fn nanoseconds("PT0.000000001S", 0, 1));
+    test_java!(fn plus("+PT+1M", 60, 0));
+    test_java!(fn negative_fraction("PT1M-0.5S", 59, 500_000_000));
+    test_java!(fn negative_seconds("PT1M-1.5S", 58, 500_000_000));
+    test_java!(fn long_hours("PT48H", 172_800, 0));
+
+    test_java_invalid!(fn empty("", Error::NoValueFound(String::new())));
+    test_java_invalid!(fn no_p("T1S", invalid("T1S")));
+    test_java_invalid!(fn p_only("P", invalid("P")));
+    test_java_invalid!(fn t_only("PT", invalid("PT")));
+    test_java_invalid!(fn empty_time("P1DT", invalid("P1DT")));
+    test_java_invalid!(fn time_without_t("P1H", invalid("P1H")));
+    test_java_invalid!(fn wrong_order("PT1S1M", invalid("PT1S1M")));
+    test_java_invalid!(fn repeated("PT1M1M", invalid("PT1M1M")));
+    test_java_invalid!(fn weeks("P1W", invalid("P1W")));
+    test_java_invalid!(fn fraction_of_minute("PT1.5M", invalid("PT1.5M")));
+    test_java_invalid!(fn ten_decimals("PT0.0000000001S", invalid("PT0.0000000001S")));
+    test_java_invalid!(fn space("PT1H 2M", invalid("PT1H 2M")));
+    test_java_invalid!(fn double_sign("PT--1S", invalid("PT--1S")));
+    test_java_invalid!(fn too_long("PT9223372036854775807H", Error::Overflow));
+    test_java_invalid!(fn too_many_digits("PT99999999999999999999S", Error::Overflow));
+    // Java's Duration holds up to Long.MAX_VALUE seconds.
+    test_java!(fn max_seconds("PT9223372036854775807S", 9_223_372_036_854_775_807, 0));
+    test_java!(fn max_seconds_fraction("PT9223372036854775807.999999999S", 9_223_372_036_854_775_807, 999_999_999));
+    test_java!(fn large_seconds("PT9000000000000000000S", 9_000_000_000_000_000_000, 0));
+    test_java!(fn max_seconds_sum("PT1M9223372036854775747S", 9_223_372_036_854_775_807, 0));
+    test_java_invalid!(fn max_seconds_sum_overflow("PT1M9223372036854775748S", Error::Overflow));
+    test_java_invalid!(fn min_seconds("PT-9223372036854775808S", Error::OutOfBounds(i64::MIN)));
+    test_java_invalid!(fn min_seconds_negated("-PT-9223372036854775808S", Error::Overflow));
+
+    #[test]
+    fn format() {
+        assert_eq!(
+            format_java(Duration::new(29_172, 345_000_000)),
+            "PT8H6M12.345S"
+        );
+        assert_eq!(format_java(Duration::new(0, 0)), "PT0S");
+        assert_eq!(format_java(Duration::new(20, 345_000_000)), "PT20.345S");
+        assert_eq!(format_java(Duration::new(900, 0)), "PT15M");
+        assert_eq!(format_java(Duration::new(183_840, 0)), "PT51H4M");
+        assert_eq!(format_java(Duration::new(0, 1)), "PT0.000000001S");
+        assert_eq!(format_java(Duration::new(3_601, 0)), "PT1H1S");
+    }
+
+    #[test]
+    fn round_trip() {
+        for &(seconds, nanoseconds) in
+            &[(0, 0), (1, 5), (59, 999_999_999), (86_400, 0), (1 << 60, 1)]
+        {
+            let duration = Duration::new(seconds, nanoseconds);
+            assert_eq!(parse_java(&format_java(duration)), Ok(duration));
+        }
+    }
+}
+
+mod dotnet {
+    use crate::parse::Error;
+    use crate::{format_dotnet, parse_dotnet};
+    use ::std::time::Duration;
+
+    macro_rules! test_dotnet {
+        (fn $fun:ident($string: expr, $seconds: expr, $nanoseconds: expr)) => {
+            #[test]
+            fn $fun() {
+                let duration = Duration::new($seconds, $nanoseconds);
+                assert_eq!(parse_dotnet($string), Ok(duration));
+                assert_eq!(format_dotnet(duration), $string);
+            }
+        };
+    }
+
+    macro_rules! test_dotnet_invalid {
+        (fn $fun:ident($string: expr, $error: expr)) => {
+            #[test]
+            fn $fun() {
+                assert_eq!(parse_dotnet($string), Err($error));
+            }
+        };
+    }
+
+    fn invalid(input: &str) -> Error {
+        Error::InvalidFormat(input.to_owned())
+    }
+
+    // The examples of the "c" format in the .NET documentation.
+    test_dotnet!(fn zero("00:00:00", 0, 0));
+    test_dotnet!(fn minutes("00:30:00", 1_800, 0));
+    test_dotnet!(fn days("3.17:25:30.5000000", 321_930, 500_000_000));
+    test_dotnet!(fn ticks("1.02:03:04.5000000", 93_784, 500_000_000));
+    test_dotnet!(fn one_tick("00:00:00.0000001", 0, 100));
+    test_dotnet!(fn max_value("10675199.02:48:05.4775807", 922_337_203_685, 477_580_700));
+
+    #[test]
+    fn short() {
+        assert_eq!(parse_dotnet("1:2:3"), Ok(Duration::new(3_723, 0)));
+        assert_eq!(
+            parse_dotnet("00:00:00.5"),
+            Ok(Duration::new(0, 500_000_000))
+        );
+        assert_eq!(parse_dotnet("-00:00:00"), Ok(Duration::new(0, 0)));
+    }
+
+    #[test]
+    fn format_rounds_down() {
+        assert_eq!(format_dotnet(Duration::new(0, 199)), "00:00:00.0000001");
+        assert_eq!(format_dotnet(Duration::new(0, 99)), "00:00:00");
+        assert_eq!(format_dotnet(Duration::new(86_400, 0)), "1.00:00:00");
+    }
+
+    test_dotnet_invalid!(fn empty("", Error::NoValueFound(String::new())));
+    test_dotnet_invalid!(fn hours_minutes("01:02", invalid("01:02")));
+    test_dotnet_invalid!(fn days_only("1", invalid("1")));
+    test_dotnet_invalid!(fn too_many_parts("1:02:03:04", invalid("1:02:03:04")));
+    test_dotnet_invalid!(fn hours_too_large("24:00:00", invalid("24:00:00")));
+    test_dotnet_invalid!(fn minutes_too_large("00:60:00", invalid("00:60:00")));
+    test_dotnet_invalid!(fn seconds_too_large("00:00:60", invalid("00:00:60")));
+    test_dotnet_invalid!(fn three_digits("000:00:00", invalid("000:00:00")));
+    test_dotnet_invalid!(fn eight_decimals("00:00:00.00000001", invalid("00:00:00.00000001")));
+    test_dotnet_invalid!(fn trailing_dot("00:00:00.", invalid("00:00:00.")));
+    test_dotnet_invalid!(fn empty_days(".00:00:00", invalid(".00:00:00")));
+    test_dotnet_invalid!(fn plus("+00:00:01", invalid("+00:00:01")));
+    test_dotnet_invalid!(fn space(" 00:00:01", invalid(" 00:00:01")));
+    test_dotnet_invalid!(fn too_long("10675199.02:48:05.4775808", Error::Overflow));
+    test_dotnet_invalid!(fn negative("-00:00:01", Error::OutOfBounds(-1)));
+}
+
+mod postgres {
+    use crate::parse::Error;
+    use crate::{format_postgres, parse_postgres, IntervalStyle, PgInterval};
+    use ::std::time::Duration;
+    use IntervalStyle::{Iso8601, Postgres, PostgresVerbose, SqlStandard};
+
+    const STYLES: [IntervalStyle; 4] = [Postgres, PostgresVerbose, SqlStandard, Iso8601];
+
+    fn interval(months: i32, days: i32, microseconds: i64) -> PgInterval {
+        PgInterval {
+            months,
+            days,
+            microseconds,
+        }
+    }
+
+    macro_rules! test_postgres {
+        (fn $fun:ident($string: expr, $months: expr, $days: expr, $microseconds: expr)) => {
+            #[test]
+            fn $fun() {
+                assert_eq!(
+                    parse_postgres($string, Postgres),
+                    Ok(interval($months, $days, $microseconds))
+                );
+            }
+        };
+    }
+
+    macro_rules! test_postgres_invalid {
+        (fn $fun:ident($string: expr, $error: expr)) => {
+            #[test]
+            fn $fun() {
+                assert_eq!(parse_postgres($string, Postgres), Err($error));
+            }
+        };
+    }
+
+    fn invalid(input: &str) -> Error {
+        Error::InvalidFormat(input.to_owned())
+    }
+
+    const TIME: i64 = 14_706_000_000;
+
+    // The examples of interval input in the PostgreSQL documentation.
+    test_postgres!(fn sql_year_month("1-2", 14, 0, 0));
+    test_postgres!(fn sql_day_time("3 4:05:06", 0, 3, TIME));
+    test_postgres!(fn traditional("1 year 2 months 3 days 4 hours 5 minutes 6 seconds", 14, 3, TIME));
+    test_postgres!(fn iso8601("P1Y2M3DT4H5M6S", 14, 3, TIME));
+    test_postgres!(fn fractional_weeks("1.5 weeks", 0, 10, 43_200_000_000));
+    test_postgres!(fn fractional_time("01:02:03.45", 0, 0, 3_723_450_000));
+    test_postgres!(fn fractional_years("1.5 years", 18, 0, 0));
+    test_postgres!(fn fractional_months("1.75 months", 1, 22, 43_200_000_000));
+    test_postgres!(fn verbose_ago("@ 1 hour ago", 0, 0, -3_600_000_000));
+
+    test_postgres!(fn output("1 year 2 mons 3 days 04:05:06.789", 14, 3, 14_706_789_000));
+    test_postgres!(fn glued_units("1year 2mons 3days", 14, 3, 0));
+    test_postgres!(fn upper_case("1 YEAR 2 Mons", 14, 0, 0));
+    test_postgres!(fn abbreviations("1 yr 2 hrs 3 mins 4 secs 5 msecs 6 usecs", 12, 0, 7_384_005_006));
+    test_postgres!(fn large_units("1 millennium 2 centuries 3 decades", 14_760, 0, 0));
+    test_postgres!(fn seconds_without_unit("90", 0, 0, 90_000_000));
+    test_postgres!(fn hours_minutes("4:05", 0, 0, 14_700_000_000));
+    test_postgres!(fn minutes_seconds("4:05.5", 0, 0, 245_500_000));
+    test_postgres!(fn long_time("100:00:00", 0, 0, 360_000_000_000));
+    test_postgres!(fn negative_time("-04:05:06", 0, 0, -TIME));
+    test_postgres!(fn mixed_signs("-1 years -2 mons +3 days -04:05:06", -14, 3, -TIME));
+    test_postgres!(fn iso8601_negative("P-1Y-2M3DT-4H-5M-6S", -14, 3, -TIME));
+    test_postgres!(fn iso8601_fraction("PT0.5S", 0, 0, 500_000));
+    test_postgres!(fn iso8601_weeks("P2W", 0, 14, 0));
+    test_postgres!(fn round_microseconds("0.0000005 s", 0, 0, 0));
+    test_postgres!(fn round_microseconds_up("0.0000015 s", 0, 0, 2));
+    test_postgres!(fn whitespace("  1 day  ", 0, 1, 0));
+
+    test_postgres_invalid!(fn empty("", Error::NoValueFound(String::new())));
+    test_postgres_invalid!(fn only_at("@", invalid("@")));
+    test_postgres_invalid!(fn unit_only("day", invalid("day")));
+    test_postgres_invalid!(fn unknown_unit("1 fortnight", Error::UnknownUnit("fortnight".to_owned())));
+    test_postgres_invalid!(fn minutes_too_large("00:60:00", invalid("00:60:00")));
+    test_postgres_invalid!(fn months_too_large("1-12", invalid("1-12")));
+    test_postgres_invalid!(fn too_many_months("2147483648 mons", Error::Overflow));
+    test_postgres_invalid!(fn too_many_days("2147483647 days 1 day", Error::Overflow));
+    test_postgres_invalid!(fn too_many_digits("99999999999999999999 s", Error::Overflow));
+    test_postgres_invalid!(fn iso8601_empty("P", invalid("P")));
+    test_postgres_invalid!(fn iso8601_empty_time("P1DT", invalid("P1DT")));
+    test_postgres_invalid!(fn iso8601_hours_in_date("P1H", invalid("P1H")));
+    test_postgres_invalid!(fn iso8601_alternative("P0001-02-03T04:05:06", invalid("P0001-02-03T04:05:06")));
+
+    #[test]
+    fn sql_standard_sign() {
+        let negative = interval(0, -3, -TIME);
+        assert_eq!(parse_postgres("-3 4:05:06", SqlStandard), Ok(negative));
+        assert_eq!(
+            parse_postgres("-3 4:05:06", Postgres),
+            Ok(interval(0, -3, TIME))
+        );
+        assert_eq!(
+            parse_postgres("-3 +4:05:06", SqlStandard),
+            Ok(interval(0, -3, TIME))
+        );
+    }
+
+    /// The examples of the output styles in the PostgreSQL documentation, with the year-month
+    /// interval, the day-time interval and the mixed interval.
+    fn check_format(style: IntervalStyle, expected: [&str; 3]) {
+        let intervals = [
+            interval(14, 0, 0),
+            interval(0, 3, TIME),
+            interval(-14, 3, -TIME),
+        ];
+        for (interval, expected) in intervals.iter().zip(expected.iter()) {
+            assert_eq!(format_postgres(interval, style), *expected);
+            assert_eq!(parse_postgres(expected, style), Ok(*interval));
+        }
+    }
+
+    #[test]
+    fn format_sql_standard() {
+        check_format(SqlStandard, ["1-2", "3 4:05:06", "-1-2 +3 -4:05:06"]);
+    }
+
+    #[test]
+    fn format_default() {
+        // The documentation writes "-1 year", but PostgreSQL only leaves the "s" out for 1.
+        check_format(
+            Postgres,
+            [
+                "1 year 2 mons",
+                "3 days 04:05:06",
+                "-1 years -2 mons +3 days -04:05:06",
+            ],
+        );
+    }
+
+    #[test]
+    fn format_verbose() {
+        check_format(
+            PostgresVerbose,
+            [
+                "@ 1 year 2 mons",
+                "@ 3 days 4 hours 5 mins 6 secs",
+                "@ 1 year 2 mons -3 days 4 hours 5 mins 6 secs ago",
+            ],
+        );
+    }
+
+    #[test]
+    fn format_iso8601() {
+        check_format(Iso8601, ["P1Y2M", "P3DT4H5M6S", "P-1Y-2M3DT-4H-5M-6S"]);
+    }
+
+    #[test]
+    fn format_zero() {
+        let expected = ["00:00:00", "@ 0", "0", "PT0S"];
+        for (&style, expected) in STYLES.iter().zip(expected.iter()) {
+            assert_eq!(format_postgres(&PgInterval::default(), style), *expected);
+        }
+    }
+
+    #[test]
+    fn format_fractions() {
+        let half = interval(0, 0, -500_000);
+        let expected = ["-00:00:00.5", "@ 0.5 secs ago", "-0:00:00.5", "PT-0.5S"];
+        for (&style, expected) in STYLES.iter().zip(expected.iter()) {
+            assert_eq!(format_postgres(&half, style), *expected);
+        }
+        let one = interval(1, 1, 3_661_000_001);
+        let expected = [
+            "1 mon 1 day 01:01:01.000001",
+            "@ 1 mon 1 day 1 hour 1 min 1.000001 secs",
+            "+0-1 +1 +1:01:01.000001",
+            "P1M1DT1H1M1.000001S",
+        ];
+        for (&style, expected) in STYLES.iter().zip(expected.iter()) {
+            assert_eq!(format_postgres(&one, style), *expected);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let intervals = [
+            interval(0, 0, 0),
+            interval(0, 0, 1),
+            interval(-1, 0, 0),
+            interval(25, -40, 86_399_999_999),
+            interval(0, 0, -1_000_000),
+            interval(i32::MAX, i32::MIN, i64::MAX),
+            interval(i32::MIN, i32::MAX, i64::MIN + 1),
+        ];
+        for interval in &intervals {
+            for &style in &STYLES {
+                let text = format_postgres(interval, style);
+                assert_eq!(parse_postgres(&text, style), Ok(*interval), "{}", text);
+            }
+        }
+    }
+
+    #[test]
+    fn durations() {
+        let interval = parse_postgres("1 year 1 mon 1 day 01:00:00", Postgres).unwrap();
+        assert_eq!(
+            interval.to_duration(),
+            Ok(Duration::new(31_557_600 + 2_592_000 + 86_400 + 3_600, 0))
+        );
+        assert_eq!(
+            PgInterval::from_duration(Duration::new(1, 1_500)),
+            Ok(interval_of(1_000_001))
+        );
+        assert_eq!(
+            PgInterval::from_duration(Duration::new(u64::MAX, 0)),
+            Err(Error::Overflow)
+        );
+        assert_eq!(
+            parse_postgres("-1 day", Postgres).unwrap().to_duration(),
+            Err(Error::OutOfBounds(-86_400))
+        );
+    }
+
+    fn interval_of(microseconds: i64) -> PgInterval {
+        interval(0, 0, microseconds)
+    }
+}
+
+mod parser {
+    use super::*;
+    use crate::{parse_with, CasePolicy, Options, Parser, Spellings};
+    use ::std::alloc::{GlobalAlloc, Layout, System};
+    use ::std::cell::Cell;
+
+    /// Counts the allocations of each thread, so that tests running in parallel do not interfere.
+    struct CountingAllocator;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            ALLOCATIONS.with(|count| count.set(count.get() + 1));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn allocations<T>(run: impl FnOnce() -> T) -> (T, usize) {
+        let before = ALLOCATIONS.with(Cell::get);
+        let result = run();
+        (result, ALLOCATIONS.with(Cell::get) - before)
+    }
+
+    #[test]
+    fn parse_does_not_allocate() {
+        for input in [
+            "1 day -1 hour",
+            "Duration: 1 hour, 15 minutes and 29.5 seconds",
+            "1½ hours 3/4 min",
+            "15",
+            "10 MICROSECONDS",
+            "2 μs",
+        ] {
+            let (result, count) = allocations(|| parse(input));
+            assert!(result.is_ok(), "{}", input);
+            assert_eq!(count, 0, "{}", input);
+        }
+    }
+
+    #[test]
+    fn parser_does_not_allocate() {
+        let options = Options::new()
+            .natural_language(true)
+            .group_separators(&['_']);
+        let mut parser = Parser::new(options);
+        // The first input may allocate the scratch memory.
+        parser.parse("two and a half hours 1_000 ms").unwrap();
+        for input in ["two and a half hours 1_000 ms", "an hour", "15"] {
+            let (result, count) = allocations(|| parser.parse(input));
+            assert!(result.is_ok(), "{}", input);
+            assert_eq!(count, 0, "{}", input);
+        }
+    }
+
+    #[test]
+    fn spellings_do_not_allocate() {
+        let options = Options::new()
+            .spellings(Spellings::systemd())
+            .case_policy(CasePolicy::RejectAmbiguous);
+        let mut parser = Parser::new(options);
+        for input in ["1 hour 30 min", "2 Days", "15"] {
+            let (result, count) = allocations(|| parser.parse(input));
+            assert!(result.is_ok(), "{}", input);
+            assert_eq!(count, 0, "{}", input);
+        }
+    }
+
+    #[test]
+    fn parse_lines_does_not_allocate() {
+        let input = "1h30m\n15\n1 day -1 hour\n".repeat(10);
+        let mut parser = Parser::default();
+        let mut lines = parser.parse_lines(input.as_bytes());
+        // The first lines may allocate the buffer, until it fits the longest line.
+        for _ in 0..3 {
+            assert!(matches!(lines.next(), Some(Ok((_, Ok(_))))));
+        }
+        for _ in 3..30 {
+            let (result, count) = allocations(|| lines.next());
+            assert!(matches!(result, Some(Ok((_, Ok(_))))));
+            assert_eq!(count, 0);
+        }
+    }
+
+    #[test]
+    fn same_as_parse_with() {
+        let options = Options::new()
+            .natural_language(true)
+            .max(Duration::new(86_400, 0));
+        let inputs = ["twenty minutes", "15", "2 days", "1 foo", "", "three"];
+        let mut parser = Parser::new(options.clone());
+        let expected: Vec<_> = inputs
+            .iter()
+            .map(|input| parse_with(input, &options))
+            .collect();
+        assert_eq!(parser.parse_many(inputs).collect::<Vec<_>>(), expected);
+        assert_eq!(
+            parser
+                .parse_many(inputs.iter().map(|input| input.to_string()))
+                .count(),
+            inputs.len()
+        );
+    }
+
+    #[test]
+    fn lone_integer() {
+        assert_eq!(parse("[15]"), Ok(Duration::new(15, 0)));
+        assert_eq!(
+            parse("15 minutes 15"),
+            Err(parse::Error::NoUnitFound("15".to_string()))
+        );
+        assert_eq!(
+            parse("15 -"),
+            Err(parse::Error::NoUnitFound("15".to_string()))
+        );
+    }
+}
+
+/// Compare [`parse`] with the semantics of zeta12ti's original `parse_duration` 2.1,
+/// which this crate forked.
+///
+/// The original used two regular expressions and big integers. It is modelled below for ASCII
+/// inputs, with `i128` for the big integers. Inputs with exponents, which the original
+/// supported and this crate rejects on purpose (RUSTSEC-2021-0041), and values outside of the
+/// range of `i128` are not compared. Every other difference must be one of the documented
+/// [`Divergence`](enum.Divergence.html)s.
+mod differential {
+    use super::*;
+    use crate::Error;
+
+    /// The result of either parser, without the details of errors.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Outcome {
+        Duration(Duration),
+        ParseInt,
+        UnknownUnit,
+        OutOfBounds,
+        NoUnitFound,
+        NoValueFound,
+        /// Only this crate: a value or sum does not fit in 64 bits.
+        Overflow,
+        /// Only this crate: a fraction like `"1/0"`.
+        DivisionByZero,
+    }
+
+    impl From<Result<Duration, Error>> for Outcome {
+        fn from(result: Result<Duration, Error>) -> Self {
+            match result {
+                Ok(duration) => Outcome::Duration(duration),
+                Err(Error::ParseInt(_)) => Outcome::ParseInt,
+                Err(Error::UnknownUnit(_)) => Outcome::UnknownUnit,
+                Err(Error::OutOfBounds(_)) => Outcome::OutOfBounds,
+                Err(Error::NoUnitFound(_)) => Outcome::NoUnitFound,
+                Err(Error::NoValueFound(_)) => Outcome::NoValueFound,
+                Err(Error::Overflow) => Outcome::Overflow,
+                Err(Error::DivisionByZero(_)) => Outcome::DivisionByZero,
+                Err(error) => panic!("parse never returns {:?}", error),
+            }
+        }
+    }
+
+    /// The intentional differences with the original.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Divergence {
+        /// Values and sums are limited to 64 bits, where the original used big integers.
+        /// Larger values are an `Overflow` or `ParseInt` error instead of a duration, or an
+        /// `OutOfBounds` error for a negative sum.
+        SixtyFourBits,
+        /// Fractions like `"1/2 h"` and `"½ h"` are values. The original read `"1/2 h"` as a `1`
+        /// without a unit, and ignored `'½'`.
+        Fractions,
+        /// A negative decimal like `"-1.5 h"` is -1.5 hours. The original read it as -0.5 hours.
+        NegativeDecimals,
+        /// A negative number of nanoseconds borrows a second, so `"1 s -1 ns"` is 999999999
+        /// nanoseconds. The original returned `OutOfBounds`.
+        NegativeNanoseconds,
+        /// There are more units, like fortnights and kiloseconds, which were unknown in the original.
+        MoreUnits,
+        /// `"Ms"` is megaseconds. The original read it as milliseconds.
+        Megaseconds,
+        /// A lone negative number like `"-15"` is an `Overflow` error. The original returned
+        /// `OutOfBounds`.
+        LoneNegative,
+    }
+
+    /// The spellings of the units that the original did not have.
+    const MORE_UNITS: [&str; 24] = [
+        "fortnight",
+        "fortnights",
+        "quarter",
+        "quarters",
+        "qtr",
+        "qtrs",
+        "decade",
+        "decades",
+        "century",
+        "centuries",
+        "jiffy",
+        "jiffies",
+        "ks",
+        "ksec",
+        "ksecs",
+        "kilosecond",
+        "kiloseconds",
+        "megasecond",
+        "megaseconds",
+        "gs",
+        "gsec",
+        "gsecs",
+        "gigasecond",
+        "gigaseconds",
+    ];
+
+    /// The unit table of the original, as (nanoseconds, seconds), with the unknown unit as the
+    /// error. This is a copy, so that changes to the units of this crate show up as differences.
+    fn original_unit(unit: &str) -> Result<(i128, i128), Outcome> {
+        let casefold = unit.to_lowercase();
+        let is = |start: &str, names: &[&str]| {
+            casefold.starts_with(start) && names.iter().any(|name| name.starts_with(&casefold))
+        };
+        Ok(if is("n", &["nanoseconds", "nsecs"]) {
+            (1, 0)
+        } else if is("mic", &["microseconds"])
+            || is("u", &["usecs"])
+            || is("\u{3bc}", &["\u{3bc}secs"])
+        {
+            (1_000, 0)
+        } else if is("mil", &["milliseconds"]) || is("ms", &["msecs"]) {
+            (1_000_000, 0)
+        } else if is("s", &["seconds", "secs"]) {
+            (0, 1)
+        } else if (is("min", &["minutes", "mins"]) || unit.starts_with('m'))
+            && is("", &["minutes", "mins"])
+        {
+            (0, 60)
+        } else if is("h", &["hours", "hrs"]) {
+            (0, 3_600)
+        } else if is("d", &["days"]) {
+            (0, 86_400)
+        } else if is("w", &["weeks"]) {
+            (0, 604_800)
+        } else if (is("mo", &["months"]) || unit.starts_with('M')) && is("", &["months"]) {
+            (0, 2_629_746)
+        } else if is("y", &["years", "yrs"]) {
+            (0, 31_556_952)
+        } else {
+            return Err(Outcome::UnknownUnit);
+        })
+    }
+
+    /// The words that may be units, which are letters and underscores.
+    fn words(input: &str) -> impl Iterator<Item = &str> {
+        input
+            .split(|c: char| !c.is_alphabetic() && c != '_')
+            .filter(|word| !word.is_empty())
+    }
+
+    fn is_word(b: u8) -> bool {
+        b.is_ascii_alphanumeric() || b == b'_'
+    }
+
+    fn digits(bytes: &[u8], mut end: usize) -> usize {
+        while bytes.get(end).is_some_and(u8::is_ascii_digit) {
+            end += 1;
+        }
+        end
+    }
+
+    /// The number, if the input is a lone number, as matched by
+    /// NUMBER_RE: `^[^\w-]*(-?\d+)[^\w-]*$`.
+    fn lone_number(input: &str) -> Option<&str> {
+        let bytes = input.as_bytes();
+        let junk = |b: &u8| !is_word(*b) && *b != b'-';
+        let start = bytes.iter().position(|b| !junk(b)).unwrap_or(bytes.len());
+        let end = bytes
+            .iter()
+            .rposition(|b| !junk(b))
+            .map_or(start, |end| end + 1);
+        let number = &input[start..end];
+        let integer = number.strip_prefix('-').unwrap_or(number);
+        Some(number).filter(|_| !integer.is_empty() && integer.bytes().all(|b| b.is_ascii_digit()))
+    }
+
+    /// The original `parse`, or `None` if the input is not modelled.
+    fn original(input: &str) -> Option<Outcome> {
+        let bytes = input.as_bytes();
+        let value = |text: &str| text.parse::<i128>().ok();
+
+        // A lone number is a number of seconds.
+        if let Some(number) = lone_number(input) {
+            let seconds = value(number)?;
+            return Some(match u64::try_from(seconds) {
+                Ok(seconds) => Outcome::Duration(Duration::new(seconds, 0)),
+                Err(_) => Outcome::OutOfBounds,
+            });
+        }
+
+        // DURATION_RE: `(?i)(?P<int>-?\d+)\.?(?P<dec>\d+)?(?:e(?P<exp>[-+]?\d+))?(?:[^\w]*(?P<unit>[\w&&[^\d]]+))?`
+        let (mut nanoseconds, mut seconds, mut found) = (0_i128, 0_i128, false);
+        let mut index = 0;
+        while index < bytes.len() {
+            let int_start = index;
+            let digit_start = index + usize::from(bytes[index] == b'-');
+            let int_end = digits(bytes, digit_start);
+            if int_end == digit_start {
+                index += 1;
+                continue;
+            }
+            found = true;
+            let mut end = int_end;
+            if bytes.get(end) == Some(&b'.') {
+                end += 1;
+            }
+            let dec_end = digits(bytes, end);
+            let dec = &input[end..dec_end];
+            end = dec_end;
+            if let Some(b'e' | b'E') = bytes.get(end) {
+                let exp_start =
+                    end + 1 + usize::from(matches!(bytes.get(end + 1), Some(b'-' | b'+')));
+                if digits(bytes, exp_start) > exp_start {
+                    return None;
+                }
+            }
+            let unit_start = (end..bytes.len())
+                .find(|&i| is_word(bytes[i]))
+                .filter(|&i| !bytes[i].is_ascii_digit());
+            let Some(unit_start) = unit_start else {
+                return Some(Outcome::NoUnitFound);
+            };
+            let unit_end = (unit_start..bytes.len())
+                .find(|&i| !is_word(bytes[i]) || bytes[i].is_ascii_digit())
+                .unwrap_or(bytes.len());
+            index = unit_end;
+
+            let int = value(&input[int_start..int_end])?;
+            let (unit_nanoseconds, unit_seconds) = match original_unit(&input[unit_start..unit_end])
+            {
+                Ok(unit) => unit,
+                Err(outcome) => return Some(outcome),
+            };
+            if dec.is_empty() {
+                nanoseconds = nanoseconds.checked_add(int.checked_mul(unit_nanoseconds)?)?;
+                seconds = seconds.checked_add(int.checked_mul(unit_seconds)?)?;
+            } else {
+                let scale = 10_i128.checked_pow(dec.len() as u32)?;
+                let boosted = int.checked_mul(scale)?.checked_add(value(dec)?)?;
+                let unit = unit_nanoseconds + unit_seconds * 1_000_000_000;
+                nanoseconds = nanoseconds.checked_add(boosted.checked_mul(unit)? / scale)?;
+            }
+        }
+        if !found {
+            return Some(Outcome::NoValueFound);
+        }
+        seconds = seconds.checked_add(nanoseconds / 1_000_000_000)?;
+        nanoseconds %= 1_000_000_000;
+        Some(match (u64::try_from(seconds), u32::try_from(nanoseconds)) {
+            (Ok(seconds), Ok(nanoseconds)) => {
+                Outcome::Duration(Duration::new(seconds, nanoseconds))
+            }
+            _ => Outcome::OutOfBounds,
+        })
+    }
+
+    /// The divergence that explains a difference between the original and this crate.
+    fn divergence(input: &str, original: Outcome, ours: Outcome) -> Option<Divergence> {
+        let bytes = input.as_bytes();
+        let negative_decimal = bytes.windows(3).enumerate().any(|(i, window)| {
+            window[0] == b'-'
+                && window[1].is_ascii_digit()
+                && bytes[i + 1..].iter().find(|b| !b.is_ascii_digit()) == Some(&b'.')
+        });
+        if input.contains(['/', '½', '¼', '¾']) {
+            Some(Divergence::Fractions)
+        } else if (original, ours) == (Outcome::OutOfBounds, Outcome::Overflow)
+            && lone_number(input).is_some_and(|number| number.starts_with('-'))
+        {
+            Some(Divergence::LoneNegative)
+        } else if matches!(ours, Outcome::Overflow | Outcome::ParseInt)
+            || (ours == Outcome::OutOfBounds && matches!(original, Outcome::Duration(_)))
+        {
+            Some(Divergence::SixtyFourBits)
+        } else if negative_decimal {
+            Some(Divergence::NegativeDecimals)
+        } else if original == Outcome::OutOfBounds && matches!(ours, Outcome::Duration(_)) {
+            Some(Divergence::NegativeNanoseconds)
+        } else if words(input).any(|word| word == "Ms") {
+            Some(Divergence::Megaseconds)
+        } else if original == Outcome::UnknownUnit
+            && words(input).any(|word| {
+                MORE_UNITS
+                    .iter()
+                    .any(|unit| unit.eq_ignore_ascii_case(word))
+            })
+        {
+            Some(Divergence::MoreUnits)
+        } else {
+            None
+        }
+    }
+
+    fn compare(input: &str) -> Option<Divergence> {
+        let original = original(input)?;
+        let ours = Outcome::from(parse(input));
+        if original == ours {
+            return None;
+        }
+        let divergence = divergence(input, original, ours);
+        assert!(
+            divergence.is_some(),
+            "{:?}: the original gives {:?}, but this crate {:?}",
+            input,
+            original,
+            ours
+        );
+        divergence
+    }
+
+    #[test]
+    fn same_as_original() {
+        for input in [
+            "1 day -1 hour",
+            "Duration: 1 hour, 15 minutes and 29 seconds",
+            "15days20seconds100milliseconds",
+            ".:++++]][][[][15[]][seconds][]:}}}}",
+            ".:++++]][][[][15[]][][]:}}}}",
+            "14 days seconds",
+            "10 seconds 20 seconds",
+            "0.126 days",
+            "1.07 ns",
+            "1. h",
+            "1.5.3 h",
+            "15 -",
+            "",
+            "hours",
+            "1 foo",
+            "1E",
+            "10d1n15y",
+            "1844670000000000000 seconds",
+            "293.5 years",
+        ] {
+            assert_eq!(compare(input), None, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn divergences() {
+        for (input, divergence) in [
+            ("1/2 h", Divergence::Fractions),
+            ("½ h", Divergence::Fractions),
+            ("-1.5 h 2 h", Divergence::NegativeDecimals),
+            ("1 s -1 ns", Divergence::NegativeNanoseconds),
+            ("1 minute -0.5 seconds", Divergence::NegativeDecimals),
+            ("1 fortnight", Divergence::MoreUnits),
+            ("1 ks", Divergence::MoreUnits),
+            ("2 ksec 1 s", Divergence::MoreUnits),
+            ("1Ms", Divergence::Megaseconds),
+            ("1 h 1 Ms", Divergence::Megaseconds),
+            ("-15", Divergence::LoneNegative),
+            ("1 day -1.5 hours", Divergence::NegativeDecimals),
+            ("9223372036854775808 seconds", Divergence::SixtyFourBits),
+            ("18446744073709551615", Divergence::SixtyFourBits),
+        ] {
+            assert_eq!(compare(input), Some(divergence), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn generated() {
+        const PIECES: [&str; 36] = [
+            "1",
+            "15",
+            "-2",
+            "0",
+            "007",
+            "2.5",
+            "-0.25",
+            ".",
+            "-",
+            " ",
+            "  ",
+            ",",
+            ":",
+            "/",
+            "_",
+            "h",
+            "hours",
+            "m",
+            "M",
+            "min",
+            "s",
+            "ns",
+            "us",
+            "ms",
+            "d",
+            "w",
+            "mo",
+            "y",
+            "ks",
+            "Ms",
+            "x",
+            "and",
+            "½",
+            "1844674407370955",
+            "9223372036854775807",
+            "\t",
+        ];
+        // A linear congruential generator, so that the inputs are the same in every run.
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = |bound: usize| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (state >> 33) as usize % bound
+        };
+        for _ in 0..20_000 {
+            let count = 1 + next(7);
+            let input: String = (0..count).map(|_| PIECES[next(PIECES.len())]).collect();
+            compare(&input);
+        }
+    }
+}
+
+mod extended {
+    use crate::parse::Error;
+    use crate::{parse, parse_extended, ExtendedDuration};
+    use ::std::time::Duration;
+
+    macro_rules! test_extended {
+        (fn $fun:ident($string: expr, $attoseconds: expr)) => {
+            #[test]
+            fn $fun() {
+                assert_eq!(
+                    parse_extended($string),
+                    Ok(ExtendedDuration::from_attoseconds($attoseconds))
+                );
+            }
+        };
+    }
+
+    macro_rules! test_extended_invalid {
+        (fn $fun:ident($string: expr, $error: expr)) => {
+            #[test]
+            fn $fun() {
+                assert_eq!(parse_extended($string), Err($error));
+            }
+        };
+    }
+
+    test_extended!(fn attoseconds("1 as", 1));
+    test_extended!(fn femtoseconds("2 fs", 2_000));
+    test_extended!(fn picoseconds("3 picoseconds", 3_000_000));
+    test_extended!(fn upper_case("3 PS", 3_000_000));
+    test_extended!(fn mixed("1ps 500fs 1as", 1_500_001));
+    test_extended!(fn decimal("1.5 fs", 1_500));
+    test_extended!(fn tiny_decimal("0.001 ps", 1_000));
+    test_extended!(fn rounded("0.0001 fs", 0));
+    test_extended!(fn fraction("1/3 ps", 333_333));
+    test_extended!(fn seconds("1 s", 1_000_000_000_000_000_000));
+    test_extended!(fn lone_integer("15", 15_000_000_000_000_000_000));
+    test_extended!(fn negative("1 ns -1.5 ns", -500_000_000));
+    test_extended!(fn decade("1 decade", 315_569_520_000_000_000_000_000_000));
+    test_extended!(fn centuries("2 centuries", 6_311_390_400_000_000_000_000_000_000));
+    test_extended!(fn jiffies("1 jiffy", 10_000_000_000_000_000));
+    test_extended!(fn megaseconds("1Ms", 1_000_000_000_000_000_000_000_000));
+    test_extended!(fn millennium("1 millennium", 31_556_952_000_000_000_000_000_000_000));
+    test_extended!(fn millennia_decimal("0.5 millennia", 15_778_476_000_000_000_000_000_000_000));
+    test_extended!(fn precise_millennia(
+        "1.000000000000000001 millennia",
+        31_556_952_000_000_000_000_000_000_000 + 31_556_952_000
+    ));
+
+    test_extended_invalid!(fn unknown_unit("1 zs", Error::UnknownUnit("zs".to_string())));
+    test_extended_invalid!(fn no_unit("1 ps 2", Error::NoUnitFound("2".to_string())));
+    test_extended_invalid!(fn no_value("ps", Error::NoValueFound("ps".to_string())));
+    test_extended_invalid!(fn exponent("1e3 ps", Error::ExpNotSupported));
+    test_extended_invalid!(fn overflow("9223372036854775807 millennia", Error::Overflow));
+
+    #[test]
+    fn same_as_parse() {
+        for input in [
+            "1 day -1 hour",
+            "15days20seconds100milliseconds",
+            "1.07 M",
+            "10d1n15y",
+            "1½ hours",
+        ] {
+            let duration = parse(input).unwrap();
+            assert_eq!(
+                parse_extended(input).map(ExtendedDuration::to_duration),
+                Ok(Ok(duration)),
+                "{}",
+                input
+            );
+            assert_eq!(parse_extended(input), Ok(ExtendedDuration::from(duration)));
+        }
+    }
+
+    #[test]
+    fn to_duration() {
+        let duration = parse_extended("1 s 1.9 ns").unwrap();
+        assert_eq!(duration.to_duration(), Ok(Duration::new(1, 1)));
+        assert_eq!(duration.as_picoseconds(), 1_000_000_001_900);
+        assert_eq!(
+            parse_extended("-1 s").unwrap().to_duration(),
+            Err(Error::OutOfBounds(-1))
+        );
+        assert_eq!(
+            parse_extended("1 millennium").unwrap().to_duration(),
+            Ok(Duration::new(31_556_952_000, 0))
+        );
+        assert_eq!(
+            parse_extended("1000000000 millennia")
+                .unwrap()
+                .to_duration(),
+            Err(Error::Overflow)
+        );
+    }
+}
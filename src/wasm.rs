@@ -0,0 +1,65 @@
+//! WebAssembly bindings, for use from JavaScript through wasm-bindgen.
+//!
+//! Durations are `bigint`s in JavaScript. Errors are thrown as an `Error` with the name
+//! `ParseDurationError`, and these extra properties:
+//!
+//! * `kind`: the variant of [`Error`](../parse/enum.Error.html), like `"UnknownUnit"`.
+//! * `start` and `end`: the part of the input that the error is about, as indices into the
+//!   JavaScript string, so that `input.slice(error.start, error.end)` works.
+
+use ::std::ops::Range;
+use ::std::time::Duration;
+
+use ::js_sys::{Error as JsError, Reflect};
+use ::wasm_bindgen::prelude::*;
+
+use crate::format::format as format_duration;
+use crate::parse::{error_span, parse, Error};
+
+/// A byte range of `input` as a range of UTF-16 code units, which JavaScript strings use.
+pub(crate) fn utf16_span(input: &str, span: Range<usize>) -> Range<usize> {
+    let start = input[..span.start].encode_utf16().count();
+    start..start + input[span].encode_utf16().count()
+}
+
+fn js_error(input: &str, error: &Error) -> JsValue {
+    let js_error = JsError::new(&error.to_string());
+    js_error.set_name("ParseDurationError");
+    let span = utf16_span(input, error_span(input, error));
+    let properties: [(&str, JsValue); 3] = [
+        ("kind", error.name().into()),
+        ("start", (span.start as u32).into()),
+        ("end", (span.end as u32).into()),
+    ];
+    for (key, value) in properties.iter() {
+        // Setting a property of a new `Error` can not fail.
+        let _ = Reflect::set(&js_error, &JsValue::from_str(key), value);
+    }
+    js_error.into()
+}
+
+fn parse_js(input: &str) -> Result<Duration, JsValue> {
+    parse(input).map_err(|error| js_error(input, &error))
+}
+
+/// Parse a duration, and return it in nanoseconds.
+///
+/// Durations of more than 2<sup>64</sup> nanoseconds, about 584 years, are an `Overflow` error.
+#[wasm_bindgen(js_name = parseNanoseconds)]
+pub fn parse_nanoseconds(input: &str) -> Result<u64, JsValue> {
+    let duration = parse_js(input)?;
+    u64::try_from(duration.as_nanos()).map_err(|_| js_error(input, &Error::Overflow))
+}
+
+/// Parse a duration, and return it in whole milliseconds, rounded down.
+#[wasm_bindgen(js_name = parseMilliseconds)]
+pub fn parse_milliseconds(input: &str) -> Result<u64, JsValue> {
+    let duration = parse_js(input)?;
+    u64::try_from(duration.as_millis()).map_err(|_| js_error(input, &Error::Overflow))
+}
+
+/// Format a duration in nanoseconds as English text, such as "1 hour 15 minutes and 29 seconds".
+#[wasm_bindgen(js_name = format)]
+pub fn format_nanoseconds(nanoseconds: u64) -> String {
+    format_duration(Duration::from_nanos(nanoseconds))
+}
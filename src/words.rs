@@ -0,0 +1,355 @@
+//! Values written as English words, like "twenty minutes", "half an hour", "a week" or
+//! "two and a half days".
+//!
+//! Number phrases are replaced by `WordNumber` tokens before the components are built,
+//! so the rest of the parser treats them like any other value.
+
+use crate::lexer::{Token, TokenKind};
+use crate::parse::{number_value, Error, Unit, Value};
+
+const HALF: Value = Value {
+    numerator: 1,
+    denominator: 2,
+};
+
+const QUARTER: Value = Value {
+    numerator: 1,
+    denominator: 4,
+};
+
+/// The value of the number words below one hundred.
+fn small_number(word: &str) -> Option<i64> {
+    const WORDS: [&str; 20] = [
+        "zero",
+        "one",
+        "two",
+        "three",
+        "four",
+        "five",
+        "six",
+        "seven",
+        "eight",
+        "nine",
+        "ten",
+        "eleven",
+        "twelve",
+        "thirteen",
+        "fourteen",
+        "fifteen",
+        "sixteen",
+        "seventeen",
+        "eighteen",
+        "nineteen",
+    ];
+    const TENS: [&str; 8] = [
+        "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+    ];
+    if let Some(index) = WORDS.iter().position(|w| w.eq_ignore_ascii_case(word)) {
+        return Some(index as i64);
+    }
+    TENS.iter()
+        .position(|w| w.eq_ignore_ascii_case(word))
+        .map(|index| 20 + 10 * index as i64)
+}
+
+/// The value of words that multiply the number before them, like "hundred".
+fn multiplier(word: &str) -> Option<i64> {
+    const WORDS: [(&str, i64); 4] = [
+        ("dozen", 12),
+        ("hundred", 100),
+        ("thousand", 1_000),
+        ("million", 1_000_000),
+    ];
+    WORDS
+        .iter()
+        .find(|(w, _)| w.eq_ignore_ascii_case(word))
+        .map(|&(_, value)| value)
+}
+
+/// The value of "half" or "quarter" and their plurals.
+fn fraction(word: &str) -> Option<Value> {
+    const WORDS: [(&str, Value); 4] = [
+        ("half", HALF),
+        ("halves", HALF),
+        ("quarter", QUARTER),
+        ("quarters", QUARTER),
+    ];
+    WORDS
+        .iter()
+        .find(|(w, _)| w.eq_ignore_ascii_case(word))
+        .map(|&(_, value)| value)
+}
+
+struct Phrases<'a, R> {
+    input: &'a str,
+    tokens: &'a [Token],
+    resolve: &'a R,
+}
+
+impl<R: Fn(&str) -> Option<Unit>> Phrases<'_, R> {
+    /// The word at `index`, if that token is a word.
+    fn word(&self, index: usize) -> Option<&str> {
+        self.tokens
+            .get(index)
+            .filter(|token| token.kind == TokenKind::Word)
+            .map(|token| token.text(self.input))
+    }
+
+    fn is(&self, index: usize, options: &[&str]) -> bool {
+        self.word(index)
+            .is_some_and(|word| options.iter().any(|o| o.eq_ignore_ascii_case(word)))
+    }
+
+    /// Skip whitespace or a hyphen between words of the same phrase.
+    fn skip_space(&self, index: usize) -> usize {
+        match self.tokens.get(index) {
+            Some(token)
+                if token.kind == TokenKind::Junk
+                    && token
+                        .text(self.input)
+                        .chars()
+                        .all(|c| c.is_whitespace() || c == '-') =>
+            {
+                index + 1
+            }
+            _ => index,
+        }
+    }
+
+    /// Numbers like "twenty five", "one hundred and five" or "a dozen".
+    /// Returns the value and the index after the phrase.
+    ///
+    /// Number words only follow each other as a tens word and a units word, like "twenty five",
+    /// so "one two" and "five five" end after the first word. A multiplier directly after
+    /// "thousand", "million" or a multiplier that is not smaller, like "thousand hundred" or
+    /// "million million", is an error.
+    fn cardinal(&self, index: usize, mut current: i64) -> Result<Option<(i64, usize)>, Error> {
+        let mut total = 0_i64;
+        let mut end = None;
+        let mut index = index;
+        // Whether there is no number word since the last multiplier, and whether the last word
+        // was a tens word like "twenty".
+        let mut empty = true;
+        let mut after_tens = false;
+        // The multiplier directly before, if there is no number word after it.
+        let mut previous = None;
+        while let Some(word) = self.word(index) {
+            if let Some(value) = small_number(word) {
+                if !(empty || after_tens && (1..10).contains(&value)) {
+                    break;
+                }
+                current = current.checked_add(value).ok_or(Error::Overflow)?;
+                empty = false;
+                after_tens = value >= 20;
+                previous = None;
+            } else if let (Some(value), true) = (multiplier(word), end.is_some() || current > 0) {
+                if previous.is_some_and(|previous| value <= previous || current == 0) {
+                    return Err(Error::InvalidFormat(self.input.to_owned()));
+                }
+                if value >= 1_000 {
+                    let scaled = current.checked_mul(value).ok_or(Error::Overflow)?;
+                    total = total.checked_add(scaled).ok_or(Error::Overflow)?;
+                    current = 0;
+                } else {
+                    current = current.checked_mul(value).ok_or(Error::Overflow)?;
+                }
+                empty = true;
+                after_tens = false;
+                previous = Some(value);
+            } else if word.eq_ignore_ascii_case("and") && end.is_some() {
+                // As in "one hundred and five", but not "two and a half".
+                let next = self.skip_space(index + 1);
+                if self.word(next).and_then(small_number).is_none() {
+                    break;
+                }
+                index = next;
+                continue;
+            } else {
+                break;
+            }
+            end = Some(index + 1);
+            index = self.skip_space(index + 1);
+        }
+        Ok(end.map(|end| (total + current, end)))
+    }
+
+    /// "half", "quarter", optionally followed by "of a" or "an", as in "half an hour".
+    fn fraction(&self, index: usize) -> Option<(Value, usize)> {
+        let value = self.fraction_word(index)?;
+        Some((value, self.article_after(index + 1)))
+    }
+
+    /// The value of the fraction word at `index`. A word that is also a unit, like "quarter",
+    /// is only a fraction if "of", "a", "an" or a unit follows, as in "a quarter of an hour"
+    /// or "a quarter hour", so that "2 quarters" keeps its unit.
+    fn fraction_word(&self, index: usize) -> Option<Value> {
+        let word = self.word(index)?;
+        let value = fraction(word)?;
+        if (self.resolve)(word).is_none() {
+            return Some(value);
+        }
+        let next = self.skip_space(index + 1);
+        let followed =
+            self.is(next, &["of", "a", "an"]) || self.word(next).and_then(self.resolve).is_some();
+        followed.then_some(value)
+    }
+
+    /// Skip "of a", "of an", "a" or "an" at `index`, as in "a quarter of an hour".
+    fn article_after(&self, index: usize) -> usize {
+        let mut next = self.skip_space(index);
+        if self.is(next, &["of"]) {
+            next = self.skip_space(next + 1);
+            if !self.is(next, &["a", "an"]) {
+                return index;
+            }
+        }
+        if self.is(next, &["a", "an"]) {
+            next + 1
+        } else {
+            index
+        }
+    }
+
+    /// "and a half" or "and a quarter" at `index`.
+    fn and_a_fraction(&self, index: usize) -> Option<(Value, usize)> {
+        let and = self.skip_space(index);
+        let article = self.skip_space(and + 1);
+        let fraction = self.skip_space(article + 1);
+        if self.is(and, &["and"]) && self.is(article, &["a", "an"]) {
+            Some((self::fraction(self.word(fraction)?)?, fraction + 1))
+        } else {
+            None
+        }
+    }
+
+    /// A number phrase starting at `index`. Returns the value and the index after the phrase.
+    fn phrase(&self, index: usize) -> Result<Option<(Value, usize)>, Error> {
+        if let Some(found) = self.fraction(index) {
+            return Ok(Some(found));
+        }
+        let cardinal = if self.is(index, &["a", "an"]) {
+            let next = self.skip_space(index + 1);
+            if let Some(found) = self.fraction(next) {
+                // "a half hour", "a quarter of an hour"
+                return Ok(Some(found));
+            } else if self.word(next).and_then(multiplier).is_some() {
+                // "a hundred", "a dozen"
+                self.cardinal(next, 1)?
+            } else if self.word(next).and_then(self.resolve).is_some() {
+                // "a minute", but not "a break"
+                Some((1, index + 1))
+            } else {
+                None
+            }
+        } else {
+            self.cardinal(index, 0)?
+        };
+        let Some((int, end)) = cardinal else {
+            return Ok(None);
+        };
+        let value = Value::integer(int);
+        if let Some((fraction, end)) = self.and_a_fraction(end) {
+            // "two and a half"
+            let value = value.checked_add(fraction).ok_or(Error::Overflow)?;
+            return Ok(Some((value, end)));
+        }
+        let next = self.skip_space(end);
+        if let Some(fraction) = self.fraction_word(next) {
+            // "three quarters of an hour"
+            let value = value.checked_mul(fraction).ok_or(Error::Overflow)?;
+            return Ok(Some((value, self.article_after(next + 1))));
+        }
+        Ok(Some((value, end)))
+    }
+}
+
+/// Replace number phrases in `tokens` by `WordNumber` tokens.
+/// `resolve` is used to find out whether "a" is a value, as in "a minute", or not, as in "a break".
+pub(crate) fn number_words(
+    input: &str,
+    tokens: Vec<Token>,
+    resolve: &impl Fn(&str) -> Option<Unit>,
+) -> Result<Vec<Token>, Error> {
+    let mut result = Vec::with_capacity(tokens.len());
+    number_words_into(input, &tokens, resolve, &mut result)?;
+    Ok(result)
+}
+
+/// [`number_words`], writing the tokens into `result`, which is cleared first.
+pub(crate) fn number_words_into(
+    input: &str,
+    tokens: &[Token],
+    resolve: &impl Fn(&str) -> Option<Unit>,
+    result: &mut Vec<Token>,
+) -> Result<(), Error> {
+    let phrases = Phrases {
+        input,
+        tokens,
+        resolve,
+    };
+    result.clear();
+    // The unit directly after the last value, for "an hour and a half".
+    let mut last_unit = None;
+    let mut index = 0;
+    while index < tokens.len() {
+        let token = tokens[index];
+        let phrase = match token.kind {
+            TokenKind::Word => phrases.phrase(index)?,
+            TokenKind::Number {
+                exponent: false, ..
+            } => phrases
+                .and_a_fraction(index + 1)
+                .and_then(|(fraction, end)| {
+                    // "2 and a half"
+                    let value = number_value(input, token).ok()?.checked_add(fraction)?;
+                    Some((value, end))
+                }),
+            _ => None,
+        };
+        if let Some((value, end)) = phrase {
+            result.push(Token {
+                kind: TokenKind::WordNumber(value),
+                start: token.start,
+                end: tokens[end - 1].end,
+            });
+            index = end;
+            continue;
+        }
+        let trailing_fraction = match token.kind {
+            TokenKind::Word => phrases.and_a_fraction(index),
+            _ => None,
+        };
+        if let (Some(unit), Some((fraction, end))) = (last_unit, trailing_fraction) {
+            // "an hour and a half" is an hour and half an hour.
+            result.push(Token {
+                kind: TokenKind::WordNumber(fraction),
+                start: token.start,
+                end: tokens[end - 1].end,
+            });
+            result.push(unit);
+            last_unit = None;
+            index = end;
+            continue;
+        }
+        let follows_value = match result.as_slice() {
+            [.., value, junk] if junk.kind == TokenKind::Junk => is_value(value),
+            [.., value] => is_value(value),
+            [] => false,
+        };
+        if token.kind == TokenKind::Word {
+            last_unit = Some(token).filter(|_| follows_value);
+        } else if token.kind != TokenKind::Junk {
+            last_unit = None;
+        }
+        result.push(token);
+        index += 1;
+    }
+    Ok(())
+}
+
+fn is_value(token: &Token) -> bool {
+    matches!(
+        token.kind,
+        TokenKind::Number { .. } | TokenKind::WordNumber(_)
+    )
+}